@@ -0,0 +1,169 @@
+use crate::{Error, Event, FrameLimiter, FrameTimer, Input, Window};
+use std::time::Duration;
+
+// Times are in seconds
+pub trait App<I: Input, W: Window<I>>: Sized {
+    fn init(window: &mut W) -> Result<Self, Error>;
+
+    // Once per frame with the frame time
+    fn update(&mut self, _window: &mut W, _delta: f32) {}
+
+    // Zero or more times per frame with a constant timestep
+    fn fixed_update(&mut self, _window: &mut W, _timestep: f32) {}
+
+    // Alpha is how far between the last two fixed updates the frame lies, for interpolation
+    fn render(&mut self, window: &mut W, alpha: f32);
+
+    // Also called when the loop ends with an error
+    fn shutdown(&mut self, _window: &mut W) {}
+
+    // Checked once per frame, closing the window ends the loop as well
+    fn should_exit(&self) -> bool {
+        false
+    }
+}
+
+pub struct Runner {
+    timestep: Duration,
+    max_frame_time: Duration,
+    max_fixed_steps: u32,
+    pause_on_focus_loss: bool,
+    paused_frame_time: Duration,
+    clear_color: [f32; 4],
+    frame_limiter: Option<FrameLimiter>,
+}
+
+impl Runner {
+    pub const DEFAULT_FIXED_UPDATES_PER_SECOND: f32 = 60.0;
+
+    // Zero, negative and non-finite rates fall back to the default, others are clamped to
+    // between 1 and 10000 updates per second
+    pub fn new(fixed_updates_per_second: f32) -> Self {
+        let fixed_updates_per_second =
+            if fixed_updates_per_second > 0.0 && fixed_updates_per_second.is_finite() {
+                fixed_updates_per_second.clamp(1.0, 10_000.0)
+            } else {
+                Runner::DEFAULT_FIXED_UPDATES_PER_SECOND
+            };
+
+        Runner {
+            timestep: Duration::from_secs_f32(1.0 / fixed_updates_per_second),
+            max_frame_time: Duration::from_millis(250),
+            max_fixed_steps: 8,
+            pause_on_focus_loss: true,
+            paused_frame_time: Duration::from_millis(100),
+            clear_color: [0.0, 0.0, 0.0, 1.0],
+            frame_limiter: None,
+        }
+    }
+
+    // Longer frames are treated as this long so a stall doesn't cause a burst of fixed updates
+    pub fn with_max_frame_time(mut self, max_frame_time: Duration) -> Self {
+        self.max_frame_time = max_frame_time;
+        self
+    }
+
+    // Time left over after this many fixed updates in one frame is dropped
+    pub fn with_max_fixed_steps(mut self, max_fixed_steps: u32) -> Self {
+        self.max_fixed_steps = max_fixed_steps.max(1);
+        self
+    }
+
+    // While paused only render is called and game time stands still
+    pub fn with_pause_on_focus_loss(mut self, pause_on_focus_loss: bool) -> Self {
+        self.pause_on_focus_loss = pause_on_focus_loss;
+        self
+    }
+
+    // Sleep between frames while paused so an unfocused window doesn't keep a core busy
+    pub fn with_paused_frame_time(mut self, paused_frame_time: Duration) -> Self {
+        self.paused_frame_time = paused_frame_time;
+        self
+    }
+
+    pub fn with_clear_color(mut self, clear_color: [f32; 4]) -> Self {
+        self.clear_color = clear_color;
+        self
+    }
+
+    pub fn with_frame_limit(mut self, target_fps: f32) -> Self {
+        self.frame_limiter = Some(FrameLimiter::new(target_fps));
+        self
+    }
+
+    pub fn timestep(&self) -> Duration {
+        self.timestep
+    }
+
+    pub fn run<I: Input, W: Window<I>, A: App<I, W>>(
+        &mut self,
+        mut window: W,
+    ) -> Result<(), Error> {
+        let mut app = A::init(&mut window)?;
+        let result = self.run_loop(&mut app, &mut window);
+        app.shutdown(&mut window);
+        result
+    }
+
+    fn run_loop<I: Input, W: Window<I>, A: App<I, W>>(
+        &mut self,
+        app: &mut A,
+        window: &mut W,
+    ) -> Result<(), Error> {
+        let mut timer = FrameTimer::new();
+        let mut accumulator = Duration::ZERO;
+        let mut focused = true;
+        let mut was_paused = false;
+
+        while window.poll_events() && !app.should_exit() {
+            if let Some(focus) = window.events().rev().find_map(|event| match event {
+                Event::Focus(focus) => Some(*focus),
+                _ => None,
+            }) {
+                focused = focus;
+            }
+
+            let delta = timer.tick().min(self.max_frame_time);
+            let paused = self.pause_on_focus_loss && !focused;
+            // Time spent paused doesn't count towards the first frame after it
+            let delta = if was_paused { Duration::ZERO } else { delta };
+            was_paused = paused;
+
+            if paused {
+                accumulator = Duration::ZERO;
+            } else {
+                app.update(window, delta.as_secs_f32());
+
+                accumulator += delta;
+                let mut steps = 0;
+                while accumulator >= self.timestep && steps < self.max_fixed_steps {
+                    app.fixed_update(window, self.timestep.as_secs_f32());
+                    accumulator -= self.timestep;
+                    steps += 1;
+                }
+
+                // Avoid the spiral of death when fixed updates can't keep up
+                if steps == self.max_fixed_steps {
+                    accumulator = Duration::from_nanos(
+                        (accumulator.as_nanos() % self.timestep.as_nanos().max(1)) as u64,
+                    );
+                }
+            }
+
+            window.begin_render(self.clear_color);
+            app.render(
+                window,
+                accumulator.as_secs_f32() / self.timestep.as_secs_f32(),
+            );
+            window.end_render()?;
+
+            if paused {
+                std::thread::sleep(self.paused_frame_time);
+            } else if let Some(frame_limiter) = &mut self.frame_limiter {
+                frame_limiter.wait();
+            }
+        }
+
+        Ok(())
+    }
+}
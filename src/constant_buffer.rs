@@ -1,15 +1,11 @@
-use crate::{Input, Window};
+use crate::{Device, Error};
 
 pub trait ConstantBuffer<T>: Sized {
-    type Window<I: Input>: Window<I>;
+    type Device: Device;
 
-    fn new<I: Input>(
-        initial_data: T,
-        slot: usize,
-        window: &mut Self::Window<I>,
-    ) -> Result<Self, Box<dyn std::error::Error>>;
+    fn new(initial_data: T, slot: usize, device: &Self::Device) -> Result<Self, Error>;
 
-    fn set_data(&mut self, new_data: T) -> Result<(), Box<dyn std::error::Error>>;
+    fn set_data(&mut self, new_data: T) -> Result<(), Error>;
     fn set_slot(&mut self, new_slot: usize);
 
     fn set_active(&mut self);
@@ -0,0 +1,64 @@
+use ginger::Pixel;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum CursorIcon {
+    #[default]
+    Arrow,
+    Hand,
+    Text,
+    Crosshair,
+    Move,
+    ResizeHorizontal,
+    ResizeVertical,
+    // Top left to bottom right
+    ResizeDiagonal,
+    // Top right to bottom left
+    ResizeAntiDiagonal,
+    NotAllowed,
+    Wait,
+    Progress,
+    Help,
+}
+
+#[derive(Clone)]
+pub struct CursorImage {
+    pixels: Vec<Pixel<u8>>,
+    width: usize,
+    height: usize,
+    hotspot: (usize, usize),
+}
+
+impl CursorImage {
+    // The hotspot is the pixel that lies under the mouse position
+    pub fn new(
+        pixels: Vec<Pixel<u8>>,
+        width: usize,
+        height: usize,
+        hotspot: (usize, usize),
+    ) -> Self {
+        assert_eq!(pixels.len(), width * height);
+        assert!(hotspot.0 < width && hotspot.1 < height);
+        CursorImage {
+            pixels,
+            width,
+            height,
+            hotspot,
+        }
+    }
+
+    pub fn pixels(&self) -> &[Pixel<u8>] {
+        &self.pixels
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn hotspot(&self) -> (usize, usize) {
+        self.hotspot
+    }
+}
@@ -0,0 +1,128 @@
+use std::fmt::Display;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Severity {
+    Debug,
+    Info,
+    Warning,
+    Error,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DebugCategory {
+    General,
+    // Incorrect API usage reported by the driver or debug layer
+    Validation,
+    Performance,
+    Shader,
+}
+
+// Receives debug messages from the device and windows
+pub trait DebugSink {
+    fn message(&mut self, severity: Severity, category: DebugCategory, message: &str);
+}
+
+pub struct StderrSink {
+    min_severity: Severity,
+}
+
+// Panics on messages at or above a severity so tests fail on backend errors, anything below is
+// passed on to the inner sink
+pub struct PanicSink {
+    min_severity: Severity,
+    inner: Option<Box<dyn DebugSink>>,
+}
+
+// Forwards to the `log` facade with a target of "alexandria::<category>"
+#[cfg(feature = "log")]
+pub struct LogSink;
+
+impl Display for Severity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Severity::Debug => "debug",
+                Severity::Info => "info",
+                Severity::Warning => "warning",
+                Severity::Error => "error",
+            }
+        )
+    }
+}
+
+impl Display for DebugCategory {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                DebugCategory::General => "general",
+                DebugCategory::Validation => "validation",
+                DebugCategory::Performance => "performance",
+                DebugCategory::Shader => "shader",
+            }
+        )
+    }
+}
+
+impl Default for StderrSink {
+    fn default() -> Self {
+        StderrSink::new(Severity::Debug)
+    }
+}
+
+impl StderrSink {
+    pub fn new(min_severity: Severity) -> Self {
+        StderrSink { min_severity }
+    }
+}
+
+impl DebugSink for StderrSink {
+    fn message(&mut self, severity: Severity, category: DebugCategory, message: &str) {
+        if severity >= self.min_severity {
+            eprintln!("[{}] [{}] {}", severity, category, message);
+        }
+    }
+}
+
+impl PanicSink {
+    pub fn new(min_severity: Severity, inner: Option<Box<dyn DebugSink>>) -> Self {
+        PanicSink {
+            min_severity,
+            inner,
+        }
+    }
+}
+
+impl DebugSink for PanicSink {
+    fn message(&mut self, severity: Severity, category: DebugCategory, message: &str) {
+        if severity >= self.min_severity {
+            panic!("[{}] [{}] {}", severity, category, message);
+        }
+
+        if let Some(inner) = &mut self.inner {
+            inner.message(severity, category, message);
+        }
+    }
+}
+
+#[cfg(feature = "log")]
+impl DebugSink for LogSink {
+    fn message(&mut self, severity: Severity, category: DebugCategory, message: &str) {
+        let level = match severity {
+            Severity::Debug => log::Level::Debug,
+            Severity::Info => log::Level::Info,
+            Severity::Warning => log::Level::Warn,
+            Severity::Error => log::Level::Error,
+        };
+        let target = match category {
+            DebugCategory::General => "alexandria::general",
+            DebugCategory::Validation => "alexandria::validation",
+            DebugCategory::Performance => "alexandria::performance",
+            DebugCategory::Shader => "alexandria::shader",
+        };
+        log::log!(target: target, level, "{}", message);
+    }
+}
@@ -0,0 +1,16 @@
+use crate::{DebugSink, Error};
+
+// Owns GPU resources, windows created with the same device can all use them. Windows share the
+// device through Rc so every method takes &self, backends keep mutable state in a Cell or RefCell.
+pub trait Device: Sized {
+    // Enables the debug layer, messages go to stderr until another sink is set
+    fn new(debug_logging: bool) -> Result<Self, Error>;
+
+    // Enables the debug layer with messages going to `sink`, the sink has to be installed before
+    // the device is created so messages emitted during creation reach it too
+    fn with_debug_sink(sink: Box<dyn DebugSink>) -> Result<Self, Error>;
+
+    // None stops delivering debug messages. The sink is called from inside backend calls, keep
+    // it in a RefCell and don't call back into the device from it.
+    fn set_debug_sink(&self, sink: Option<Box<dyn DebugSink>>);
+}
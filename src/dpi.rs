@@ -0,0 +1,62 @@
+use crate::Vector2;
+
+// Ratio of physical pixels to logical units, 1.0 at 96 DPI
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct ScaleFactor(f32);
+
+impl ScaleFactor {
+    pub const ONE: ScaleFactor = ScaleFactor(1.0);
+    pub const BASE_DPI: f32 = 96.0;
+
+    pub fn new(scale_factor: f32) -> Self {
+        assert!(scale_factor > 0.0 && scale_factor.is_finite());
+        ScaleFactor(scale_factor)
+    }
+
+    pub fn from_dpi(dpi: f32) -> Self {
+        ScaleFactor::new(dpi / ScaleFactor::BASE_DPI)
+    }
+
+    pub fn value(&self) -> f32 {
+        self.0
+    }
+
+    pub fn dpi(&self) -> f32 {
+        self.0 * ScaleFactor::BASE_DPI
+    }
+
+    pub fn to_logical(&self, physical: Vector2) -> Vector2 {
+        physical / self.0
+    }
+
+    pub fn to_physical(&self, logical: Vector2) -> Vector2 {
+        logical * self.0
+    }
+
+    pub fn logical_position(&self, position: (isize, isize)) -> Vector2 {
+        self.to_logical(Vector2::new(position.0 as f32, position.1 as f32))
+    }
+
+    pub fn physical_position(&self, position: Vector2) -> (isize, isize) {
+        let position = self.to_physical(position);
+        (position.x().round() as isize, position.y().round() as isize)
+    }
+
+    pub fn logical_size(&self, size: (usize, usize)) -> Vector2 {
+        self.to_logical(Vector2::new(size.0 as f32, size.1 as f32))
+    }
+
+    pub fn physical_size(&self, size: Vector2) -> (usize, usize) {
+        let size = self.to_physical(size);
+        (
+            size.x().round().max(0.0) as usize,
+            size.y().round().max(0.0) as usize,
+        )
+    }
+}
+
+impl Default for ScaleFactor {
+    fn default() -> Self {
+        ScaleFactor::ONE
+    }
+}
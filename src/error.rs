@@ -0,0 +1,111 @@
+use crate::ViewportError;
+use std::fmt::Display;
+
+#[derive(Debug)]
+pub enum Error {
+    ShaderCompilation(Vec<ShaderDiagnostic>),
+    InvalidArgument(String),
+    UnsupportedFormat(String),
+    // Out of GPU memory, descriptors or other limited resources
+    ResourceExhausted(String),
+    // The device was reset or removed, everything created on it has to be recreated
+    DeviceLost,
+    InvalidViewport(ViewportError),
+    Io(std::io::Error),
+    // Platform or backend failures that don't fit another variant
+    Other(Box<dyn std::error::Error + Send + Sync>),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ShaderDiagnostic {
+    line: Option<usize>,
+    column: Option<usize>,
+    message: String,
+}
+
+impl Error {
+    pub fn other<E: Into<Box<dyn std::error::Error + Send + Sync>>>(error: E) -> Self {
+        Error::Other(error.into())
+    }
+
+    pub fn is_device_lost(&self) -> bool {
+        matches!(self, Error::DeviceLost)
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::InvalidViewport(error) => Some(error),
+            Error::Io(error) => Some(error),
+            Error::Other(error) => Some(error.as_ref()),
+            _ => None,
+        }
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::ShaderCompilation(diagnostics) => {
+                write!(f, "Shader compilation failed")?;
+                for diagnostic in diagnostics {
+                    write!(f, "\n{}", diagnostic)?;
+                }
+                Ok(())
+            }
+            Error::InvalidArgument(message) => write!(f, "Invalid argument: {}", message),
+            Error::UnsupportedFormat(message) => write!(f, "Unsupported format: {}", message),
+            Error::ResourceExhausted(message) => write!(f, "Resource exhausted: {}", message),
+            Error::DeviceLost => write!(f, "Device lost"),
+            Error::InvalidViewport(error) => error.fmt(f),
+            Error::Io(error) => error.fmt(f),
+            Error::Other(error) => error.fmt(f),
+        }
+    }
+}
+
+impl From<ViewportError> for Error {
+    fn from(error: ViewportError) -> Self {
+        Error::InvalidViewport(error)
+    }
+}
+
+impl From<std::io::Error> for Error {
+    fn from(error: std::io::Error) -> Self {
+        Error::Io(error)
+    }
+}
+
+impl ShaderDiagnostic {
+    // Line and column start at 1
+    pub fn new<S: Into<String>>(line: Option<usize>, column: Option<usize>, message: S) -> Self {
+        ShaderDiagnostic {
+            line,
+            column,
+            message: message.into(),
+        }
+    }
+
+    pub fn line(&self) -> Option<usize> {
+        self.line
+    }
+
+    pub fn column(&self) -> Option<usize> {
+        self.column
+    }
+
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+impl Display for ShaderDiagnostic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match (self.line, self.column) {
+            (Some(line), Some(column)) => write!(f, "{}:{}: {}", line, column, self.message),
+            (Some(line), None) => write!(f, "{}: {}", line, self.message),
+            _ => write!(f, "{}", self.message),
+        }
+    }
+}
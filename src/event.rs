@@ -0,0 +1,91 @@
+use crate::{Input, Key, MouseButton, ScaleFactor, TouchPoint, WindowMode};
+use std::path::PathBuf;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Event {
+    KeyDown(Key),
+    KeyUp(Key),
+    KeyRepeat(Key),
+    MouseDown(MouseButton),
+    MouseUp(MouseButton),
+    MouseMove(isize, isize),
+    Text(char),
+    Touch(TouchPoint),
+    Resize { width: usize, height: usize },
+    ModeChanged(WindowMode),
+    Focus(bool),
+    CloseRequested,
+    DpiChanged(ScaleFactor),
+    FileDrop(PathBuf),
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct EventQueue {
+    events: Vec<Event>,
+}
+
+impl Event {
+    // Window events have no effect on the input
+    pub fn apply<I: Input>(&self, input: &mut I) {
+        match *self {
+            Event::KeyDown(key) => input.key_down(key),
+            Event::KeyUp(key) => input.key_up(key),
+            Event::KeyRepeat(key) => input.key_repeat(key),
+            Event::MouseDown(button) => input.mouse_down(button),
+            Event::MouseUp(button) => input.mouse_up(button),
+            Event::MouseMove(x, y) => input.update_mouse_position((x, y)),
+            Event::Touch(touch) => input.touch(touch),
+            _ => {}
+        }
+    }
+
+    pub fn is_input(&self) -> bool {
+        matches!(
+            self,
+            Event::KeyDown(_)
+                | Event::KeyUp(_)
+                | Event::KeyRepeat(_)
+                | Event::MouseDown(_)
+                | Event::MouseUp(_)
+                | Event::MouseMove(_, _)
+                | Event::Text(_)
+                | Event::Touch(_)
+        )
+    }
+}
+
+impl EventQueue {
+    pub fn new() -> Self {
+        EventQueue { events: Vec::new() }
+    }
+
+    pub fn push<I: Input>(&mut self, event: Event, input: &mut I) {
+        event.apply(input);
+        self.events.push(event);
+    }
+
+    pub fn clear(&mut self) {
+        self.events.clear();
+    }
+
+    pub fn iter(&self) -> std::slice::Iter<'_, Event> {
+        self.events.iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.events.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.events.is_empty()
+    }
+}
+
+impl<'a> IntoIterator for &'a EventQueue {
+    type Item = &'a Event;
+    type IntoIter = std::slice::Iter<'a, Event>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.events.iter()
+    }
+}
@@ -0,0 +1,328 @@
+use std::{
+    fmt::Display,
+    str::FromStr,
+    time::{Duration, Instant},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum PresentMode {
+    Immediate,
+    #[default]
+    VSync,
+    // Waits for vertical blank unless the frame is late, in which case it tears
+    AdaptiveVSync,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParsePresentModeError(String);
+
+// Paces a loop to a target frame rate by sleeping most of the frame and spinning the rest
+pub struct FrameLimiter {
+    frame_duration: Duration,
+    spin_duration: Duration,
+    next_frame: Option<Instant>,
+}
+
+pub struct FrameTimer {
+    start: Instant,
+    last_frame: Instant,
+    delta: Duration,
+    frame_count: u64,
+    average_frame_time: f32,
+    smoothing: f32,
+    histogram: FrameHistogram,
+}
+
+// Frame times bucketed by a fixed width, the last bucket collects everything longer
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FrameHistogram {
+    bucket_width: Duration,
+    buckets: Vec<u64>,
+    total: u64,
+}
+
+impl PresentMode {
+    pub fn is_synced(&self) -> bool {
+        !matches!(self, PresentMode::Immediate)
+    }
+}
+
+impl FromStr for PresentMode {
+    type Err = ParsePresentModeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "immediate" | "off" => Ok(PresentMode::Immediate),
+            "vsync" | "on" => Ok(PresentMode::VSync),
+            "adaptive" | "adaptive vsync" => Ok(PresentMode::AdaptiveVSync),
+            _ => Err(ParsePresentModeError(s.to_owned())),
+        }
+    }
+}
+
+impl Display for PresentMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                PresentMode::Immediate => "immediate",
+                PresentMode::VSync => "vsync",
+                PresentMode::AdaptiveVSync => "adaptive",
+            }
+        )
+    }
+}
+
+impl std::error::Error for ParsePresentModeError {}
+
+impl Display for ParsePresentModeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Invalid present mode \"{}\"", self.0)
+    }
+}
+
+impl FrameLimiter {
+    pub const DEFAULT_SPIN_DURATION: Duration = Duration::from_millis(2);
+
+    // Zero, negative and non-finite rates disable the limit
+    pub fn new(target_fps: f32) -> Self {
+        FrameLimiter {
+            frame_duration: frame_duration(target_fps),
+            spin_duration: FrameLimiter::DEFAULT_SPIN_DURATION,
+            next_frame: None,
+        }
+    }
+
+    // 0.0 when unlimited
+    pub fn target_fps(&self) -> f32 {
+        if self.frame_duration.is_zero() {
+            0.0
+        } else {
+            1.0 / self.frame_duration.as_secs_f32()
+        }
+    }
+
+    pub fn set_target_fps(&mut self, target_fps: f32) {
+        self.frame_duration = frame_duration(target_fps);
+    }
+
+    pub fn frame_duration(&self) -> Duration {
+        self.frame_duration
+    }
+
+    // Sleep precision varies by platform, the last part of each frame is busy-waited instead
+    pub fn set_spin_duration(&mut self, spin_duration: Duration) {
+        self.spin_duration = spin_duration;
+    }
+
+    pub fn spin_duration(&self) -> Duration {
+        self.spin_duration
+    }
+
+    // Blocks until the next frame should start, call once per frame after presenting
+    pub fn wait(&mut self) {
+        let now = Instant::now();
+        let deadline = match self.next_frame {
+            Some(deadline) => deadline,
+            None => {
+                self.next_frame = Some(now + self.frame_duration);
+                return;
+            }
+        };
+
+        if let Some(remaining) = deadline.checked_duration_since(now) {
+            if remaining > self.spin_duration {
+                std::thread::sleep(remaining - self.spin_duration);
+            }
+            while Instant::now() < deadline {
+                std::hint::spin_loop();
+            }
+        }
+
+        // A late frame starts a new schedule instead of rushing the following ones
+        let now = Instant::now();
+        let next = deadline + self.frame_duration;
+        self.next_frame = Some(if next < now {
+            now + self.frame_duration
+        } else {
+            next
+        });
+    }
+
+    pub fn reset(&mut self) {
+        self.next_frame = None;
+    }
+}
+
+impl FrameTimer {
+    pub const DEFAULT_SMOOTHING: f32 = 0.9;
+
+    pub fn new() -> Self {
+        let now = Instant::now();
+        FrameTimer {
+            start: now,
+            last_frame: now,
+            delta: Duration::ZERO,
+            frame_count: 0,
+            average_frame_time: 0.0,
+            smoothing: FrameTimer::DEFAULT_SMOOTHING,
+            histogram: FrameHistogram::default(),
+        }
+    }
+
+    // Call once per frame, returns the time since the previous call
+    pub fn tick(&mut self) -> Duration {
+        self.tick_at(Instant::now())
+    }
+
+    pub fn tick_at(&mut self, time: Instant) -> Duration {
+        self.delta = time.saturating_duration_since(self.last_frame);
+        self.last_frame = time;
+        self.frame_count += 1;
+
+        let seconds = self.delta.as_secs_f32();
+        self.average_frame_time = if self.frame_count == 1 {
+            seconds
+        } else {
+            self.average_frame_time * self.smoothing + seconds * (1.0 - self.smoothing)
+        };
+
+        self.histogram.record(self.delta);
+        self.delta
+    }
+
+    pub fn delta(&self) -> Duration {
+        self.delta
+    }
+
+    pub fn delta_seconds(&self) -> f32 {
+        self.delta.as_secs_f32()
+    }
+
+    // Exponential moving average of the frame time, weighted by the smoothing factor
+    pub fn average_frame_time(&self) -> Duration {
+        Duration::from_secs_f32(self.average_frame_time)
+    }
+
+    pub fn fps(&self) -> f32 {
+        if self.average_frame_time > 0.0 {
+            1.0 / self.average_frame_time
+        } else {
+            0.0
+        }
+    }
+
+    // 0 reports the instantaneous rate, values closer to 1 smooth more
+    pub fn set_smoothing(&mut self, smoothing: f32) {
+        self.smoothing = smoothing.clamp(0.0, 1.0);
+    }
+
+    pub fn frame_count(&self) -> u64 {
+        self.frame_count
+    }
+
+    pub fn elapsed(&self) -> Duration {
+        self.last_frame.saturating_duration_since(self.start)
+    }
+
+    pub fn histogram(&self) -> &FrameHistogram {
+        &self.histogram
+    }
+
+    pub fn histogram_mut(&mut self) -> &mut FrameHistogram {
+        &mut self.histogram
+    }
+
+    pub fn reset(&mut self) {
+        let now = Instant::now();
+        self.start = now;
+        self.last_frame = now;
+        self.delta = Duration::ZERO;
+        self.frame_count = 0;
+        self.average_frame_time = 0.0;
+        self.histogram.clear();
+    }
+}
+
+impl Default for FrameTimer {
+    fn default() -> Self {
+        FrameTimer::new()
+    }
+}
+
+impl FrameHistogram {
+    pub fn new(bucket_width: Duration, bucket_count: usize) -> Self {
+        assert!(bucket_count > 0);
+        FrameHistogram {
+            bucket_width,
+            buckets: vec![0; bucket_count],
+            total: 0,
+        }
+    }
+
+    pub fn record(&mut self, frame_time: Duration) {
+        let index = (frame_time.as_nanos() / self.bucket_width.as_nanos().max(1)) as usize;
+        let index = index.min(self.buckets.len() - 1);
+        self.buckets[index] += 1;
+        self.total += 1;
+    }
+
+    pub fn bucket_width(&self) -> Duration {
+        self.bucket_width
+    }
+
+    pub fn buckets(&self) -> &[u64] {
+        &self.buckets
+    }
+
+    // Start of each bucket and the number of frames in it
+    pub fn iter(&self) -> impl Iterator<Item = (Duration, u64)> + '_ {
+        self.buckets
+            .iter()
+            .enumerate()
+            .map(|(i, count)| (self.bucket_width * i as u32, *count))
+    }
+
+    pub fn total(&self) -> u64 {
+        self.total
+    }
+
+    // Upper bound of the bucket containing the given fraction of frames, e.g. 0.99
+    pub fn percentile(&self, fraction: f32) -> Option<Duration> {
+        if self.total == 0 {
+            return None;
+        }
+
+        let target = ((self.total as f64 * fraction.clamp(0.0, 1.0) as f64).ceil() as u64).max(1);
+        let mut count = 0;
+        for (i, bucket) in self.buckets.iter().enumerate() {
+            count += bucket;
+            if count >= target {
+                return Some(self.bucket_width * (i as u32 + 1));
+            }
+        }
+        None
+    }
+
+    pub fn clear(&mut self) {
+        self.buckets.iter_mut().for_each(|bucket| *bucket = 0);
+        self.total = 0;
+    }
+}
+
+// 1ms buckets up to 100ms
+impl Default for FrameHistogram {
+    fn default() -> Self {
+        FrameHistogram::new(Duration::from_millis(1), 100)
+    }
+}
+
+// Rates below one frame every 100 seconds are clamped so the duration stays representable
+fn frame_duration(target_fps: f32) -> Duration {
+    if target_fps > 0.0 && target_fps.is_finite() {
+        Duration::from_secs_f32(1.0 / target_fps.max(0.01))
+    } else {
+        Duration::ZERO
+    }
+}
@@ -0,0 +1,58 @@
+use crate::{Key, MouseButton};
+use std::io::{Error, ErrorKind, Read, Write};
+
+pub(super) fn invalid_data(message: &str) -> Error {
+    Error::new(ErrorKind::InvalidData, message)
+}
+
+pub(super) fn read_u8<R: Read>(reader: &mut R) -> std::io::Result<u8> {
+    let mut byte = [0];
+    reader.read_exact(&mut byte)?;
+    Ok(byte[0])
+}
+
+pub(super) fn read_key<R: Read>(reader: &mut R) -> std::io::Result<Key> {
+    Key::try_from(read_u8(reader)?).map_err(|_| invalid_data("Invalid key"))
+}
+
+pub(super) fn read_button<R: Read>(reader: &mut R) -> std::io::Result<MouseButton> {
+    MouseButton::try_from(read_u8(reader)?).map_err(|_| invalid_data("Invalid mouse button"))
+}
+
+pub(super) fn read_f32<R: Read>(reader: &mut R) -> std::io::Result<f32> {
+    let mut bytes = [0; 4];
+    reader.read_exact(&mut bytes)?;
+    Ok(f32::from_le_bytes(bytes))
+}
+
+pub(super) fn write_varint<W: Write>(writer: &mut W, mut value: u64) -> std::io::Result<()> {
+    loop {
+        let byte = (value & 0x7F) as u8;
+        value >>= 7;
+        if value == 0 {
+            return writer.write_all(&[byte]);
+        }
+        writer.write_all(&[byte | 0x80])?;
+    }
+}
+
+pub(super) fn read_varint<R: Read>(reader: &mut R) -> std::io::Result<u64> {
+    let mut value = 0;
+    for shift in (0..64).step_by(7) {
+        let byte = read_u8(reader)?;
+        value |= ((byte & 0x7F) as u64) << shift;
+        if byte & 0x80 == 0 {
+            return Ok(value);
+        }
+    }
+    Err(invalid_data("Varint too long"))
+}
+
+pub(super) fn zigzag(value: isize) -> u64 {
+    let value = value as i64;
+    ((value << 1) ^ (value >> 63)) as u64
+}
+
+pub(super) fn unzigzag(value: u64) -> isize {
+    ((value >> 1) as i64 ^ -((value & 1) as i64)) as isize
+}
@@ -0,0 +1,541 @@
+use std::{fmt::Display, str::FromStr};
+
+#[repr(u8)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Key {
+    Backspace = 0x08,
+    Tab,
+    Enter = 0x0D,
+    LeftShift,
+    RightShift,
+    LeftControl,
+    RightControl,
+    LeftAlt,
+    RightAlt,
+    Pause,
+    CapsLock,
+    Escape = 0x1B,
+    Space = b' ',
+    PageUp,
+    PageDown,
+    End,
+    Home,
+    LeftArrow,
+    UpArrow,
+    RightArrow,
+    DownArrow,
+    PrintScreen = 0x2C,
+    Insert,
+    Delete,
+    _0 = b'0',
+    _1,
+    _2,
+    _3,
+    _4,
+    _5,
+    _6,
+    _7,
+    _8,
+    _9,
+    A = b'A',
+    B,
+    C,
+    D,
+    E,
+    F,
+    G,
+    H,
+    I,
+    J,
+    K,
+    L,
+    M,
+    N,
+    O,
+    P,
+    Q,
+    R,
+    S,
+    T,
+    U,
+    V,
+    W,
+    X,
+    Y,
+    Z,
+    Windows,
+    Menu,
+    Numpad0 = 0x60,
+    Numpad1,
+    Numpad2,
+    Numpad3,
+    Numpad4,
+    Numpad5,
+    Numpad6,
+    Numpad7,
+    Numpad8,
+    Numpad9,
+    NumpadMultiply,
+    NumpadAdd,
+    NumpadSubtract,
+    NumpadDecimal,
+    NumpadDivide,
+    NumpadEnter,
+    F1,
+    F2,
+    F3,
+    F4,
+    F5,
+    F6,
+    F7,
+    F8,
+    F9,
+    F10,
+    F11,
+    F12,
+    Tilde,
+    Dash,
+    Equal,
+    NumLock = 0x90,
+    ScrollLock,
+    Comma,
+    Period,
+    ForwardSlash,
+    BackSlash,
+    LeftSquareBracket,
+    RightSquareBracket,
+    SemiColon,
+    Quote,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseKeyError(String);
+
+impl Key {
+    pub const ALL: [Key; 103] = [
+        Key::Backspace,
+        Key::Tab,
+        Key::Enter,
+        Key::LeftShift,
+        Key::RightShift,
+        Key::LeftControl,
+        Key::RightControl,
+        Key::LeftAlt,
+        Key::RightAlt,
+        Key::Pause,
+        Key::CapsLock,
+        Key::Escape,
+        Key::Space,
+        Key::PageUp,
+        Key::PageDown,
+        Key::End,
+        Key::Home,
+        Key::LeftArrow,
+        Key::UpArrow,
+        Key::RightArrow,
+        Key::DownArrow,
+        Key::PrintScreen,
+        Key::Insert,
+        Key::Delete,
+        Key::_0,
+        Key::_1,
+        Key::_2,
+        Key::_3,
+        Key::_4,
+        Key::_5,
+        Key::_6,
+        Key::_7,
+        Key::_8,
+        Key::_9,
+        Key::A,
+        Key::B,
+        Key::C,
+        Key::D,
+        Key::E,
+        Key::F,
+        Key::G,
+        Key::H,
+        Key::I,
+        Key::J,
+        Key::K,
+        Key::L,
+        Key::M,
+        Key::N,
+        Key::O,
+        Key::P,
+        Key::Q,
+        Key::R,
+        Key::S,
+        Key::T,
+        Key::U,
+        Key::V,
+        Key::W,
+        Key::X,
+        Key::Y,
+        Key::Z,
+        Key::Windows,
+        Key::Menu,
+        Key::Numpad0,
+        Key::Numpad1,
+        Key::Numpad2,
+        Key::Numpad3,
+        Key::Numpad4,
+        Key::Numpad5,
+        Key::Numpad6,
+        Key::Numpad7,
+        Key::Numpad8,
+        Key::Numpad9,
+        Key::NumpadMultiply,
+        Key::NumpadAdd,
+        Key::NumpadSubtract,
+        Key::NumpadDecimal,
+        Key::NumpadDivide,
+        Key::NumpadEnter,
+        Key::F1,
+        Key::F2,
+        Key::F3,
+        Key::F4,
+        Key::F5,
+        Key::F6,
+        Key::F7,
+        Key::F8,
+        Key::F9,
+        Key::F10,
+        Key::F11,
+        Key::F12,
+        Key::Tilde,
+        Key::Dash,
+        Key::Equal,
+        Key::NumLock,
+        Key::ScrollLock,
+        Key::Comma,
+        Key::Period,
+        Key::ForwardSlash,
+        Key::BackSlash,
+        Key::LeftSquareBracket,
+        Key::RightSquareBracket,
+        Key::SemiColon,
+        Key::Quote,
+    ];
+
+    pub fn iter() -> impl Iterator<Item = Key> {
+        Key::ALL.into_iter()
+    }
+
+    fn from_alias(alias: &str) -> Option<Key> {
+        Some(match alias {
+            "bksp" | "back" => Key::Backspace,
+            "return" => Key::Enter,
+            "shift" | "lshift" => Key::LeftShift,
+            "rshift" => Key::RightShift,
+            "lctrl" | "left ctrl" => Key::LeftControl,
+            "rctrl" | "right ctrl" => Key::RightControl,
+            "lalt" | "option" | "left option" => Key::LeftAlt,
+            "ralt" | "altgr" | "right option" => Key::RightAlt,
+            "break" => Key::Pause,
+            "caps" | "capital" => Key::CapsLock,
+            "esc" => Key::Escape,
+            "spacebar" | " " => Key::Space,
+            "pgup" | "prior" => Key::PageUp,
+            "pgdn" | "next" => Key::PageDown,
+            "left arrow" | "arrowleft" => Key::LeftArrow,
+            "up arrow" | "arrowup" => Key::UpArrow,
+            "right arrow" | "arrowright" => Key::RightArrow,
+            "down arrow" | "arrowdown" => Key::DownArrow,
+            "prtsc" | "prtscn" | "print" | "snapshot" => Key::PrintScreen,
+            "ins" => Key::Insert,
+            "del" => Key::Delete,
+            "win" | "lwin" | "super" | "meta" | "cmd" | "command" => Key::Windows,
+            "apps" | "application" | "context menu" => Key::Menu,
+            "numpad multiply" | "kp*" => Key::NumpadMultiply,
+            "numpad add" | "numpad plus" | "kp+" => Key::NumpadAdd,
+            "numpad subtract" | "numpad minus" | "kp-" => Key::NumpadSubtract,
+            "numpad decimal" | "numpad period" | "kp." => Key::NumpadDecimal,
+            "numpad divide" | "kp/" => Key::NumpadDivide,
+            "kpenter" => Key::NumpadEnter,
+            "tilde" | "grave" | "backtick" | "~" => Key::Tilde,
+            "dash" | "minus" | "hyphen" => Key::Dash,
+            "equal" | "equals" => Key::Equal,
+            "numlk" => Key::NumLock,
+            "scrlk" => Key::ScrollLock,
+            "comma" => Key::Comma,
+            "period" | "dot" => Key::Period,
+            "slash" | "forward slash" => Key::ForwardSlash,
+            "backslash" | "back slash" => Key::BackSlash,
+            "left bracket" | "lbracket" => Key::LeftSquareBracket,
+            "right bracket" | "rbracket" => Key::RightSquareBracket,
+            "semicolon" | "semi colon" => Key::SemiColon,
+            "quote" | "apostrophe" => Key::Quote,
+            "kp0" => Key::Numpad0,
+            "kp1" => Key::Numpad1,
+            "kp2" => Key::Numpad2,
+            "kp3" => Key::Numpad3,
+            "kp4" => Key::Numpad4,
+            "kp5" => Key::Numpad5,
+            "kp6" => Key::Numpad6,
+            "kp7" => Key::Numpad7,
+            "kp8" => Key::Numpad8,
+            "kp9" => Key::Numpad9,
+            _ => return None,
+        })
+    }
+
+    fn from_name(name: &str) -> Option<Key> {
+        Some(match name {
+            "backspace" => Key::Backspace,
+            "tab" => Key::Tab,
+            "enter" => Key::Enter,
+            "left shift" => Key::LeftShift,
+            "right shift" => Key::RightShift,
+            "left control" => Key::LeftControl,
+            "right control" => Key::RightControl,
+            "left alt" => Key::LeftAlt,
+            "right alt" => Key::RightAlt,
+            "pause" => Key::Pause,
+            "caps lock" => Key::CapsLock,
+            "escape" => Key::Escape,
+            "space" => Key::Space,
+            "page up" => Key::PageUp,
+            "page down" => Key::PageDown,
+            "end" => Key::End,
+            "home" => Key::Home,
+            "left" => Key::LeftArrow,
+            "up" => Key::UpArrow,
+            "right" => Key::RightArrow,
+            "down" => Key::DownArrow,
+            "print screen" => Key::PrintScreen,
+            "insert" => Key::Insert,
+            "delete" => Key::Delete,
+            "0" => Key::_0,
+            "1" => Key::_1,
+            "2" => Key::_2,
+            "3" => Key::_3,
+            "4" => Key::_4,
+            "5" => Key::_5,
+            "6" => Key::_6,
+            "7" => Key::_7,
+            "8" => Key::_8,
+            "9" => Key::_9,
+            "a" => Key::A,
+            "b" => Key::B,
+            "c" => Key::C,
+            "d" => Key::D,
+            "e" => Key::E,
+            "f" => Key::F,
+            "g" => Key::G,
+            "h" => Key::H,
+            "i" => Key::I,
+            "j" => Key::J,
+            "k" => Key::K,
+            "l" => Key::L,
+            "m" => Key::M,
+            "n" => Key::N,
+            "o" => Key::O,
+            "p" => Key::P,
+            "q" => Key::Q,
+            "r" => Key::R,
+            "s" => Key::S,
+            "t" => Key::T,
+            "u" => Key::U,
+            "v" => Key::V,
+            "w" => Key::W,
+            "x" => Key::X,
+            "y" => Key::Y,
+            "z" => Key::Z,
+            "windows" => Key::Windows,
+            "menu" => Key::Menu,
+            "numpad 0" => Key::Numpad0,
+            "numpad 1" => Key::Numpad1,
+            "numpad 2" => Key::Numpad2,
+            "numpad 3" => Key::Numpad3,
+            "numpad 4" => Key::Numpad4,
+            "numpad 5" => Key::Numpad5,
+            "numpad 6" => Key::Numpad6,
+            "numpad 7" => Key::Numpad7,
+            "numpad 8" => Key::Numpad8,
+            "numpad 9" => Key::Numpad9,
+            "numpad *" => Key::NumpadMultiply,
+            "numpad +" => Key::NumpadAdd,
+            "numpad -" => Key::NumpadSubtract,
+            "numpad ." => Key::NumpadDecimal,
+            "numpad /" => Key::NumpadDivide,
+            "numpad enter" => Key::NumpadEnter,
+            "f1" => Key::F1,
+            "f2" => Key::F2,
+            "f3" => Key::F3,
+            "f4" => Key::F4,
+            "f5" => Key::F5,
+            "f6" => Key::F6,
+            "f7" => Key::F7,
+            "f8" => Key::F8,
+            "f9" => Key::F9,
+            "f10" => Key::F10,
+            "f11" => Key::F11,
+            "f12" => Key::F12,
+            "`" => Key::Tilde,
+            "-" => Key::Dash,
+            "=" => Key::Equal,
+            "num lock" => Key::NumLock,
+            "scroll lock" => Key::ScrollLock,
+            "," => Key::Comma,
+            "." => Key::Period,
+            "/" => Key::ForwardSlash,
+            "\\" => Key::BackSlash,
+            "[" => Key::LeftSquareBracket,
+            "]" => Key::RightSquareBracket,
+            ";" => Key::SemiColon,
+            "'" => Key::Quote,
+            _ => return None,
+        })
+    }
+}
+
+impl TryFrom<u8> for Key {
+    type Error = u8;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        Key::ALL
+            .into_iter()
+            .find(|item| *item as u8 == value)
+            .ok_or(value)
+    }
+}
+
+impl FromStr for Key {
+    type Err = ParseKeyError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let name = s.to_ascii_lowercase();
+        Key::from_name(&name)
+            .or_else(|| Key::from_alias(&name))
+            .ok_or_else(|| ParseKeyError(s.to_owned()))
+    }
+}
+
+impl Display for Key {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Key::Backspace => "Backspace",
+                Key::Tab => "Tab",
+                Key::Enter => "Enter",
+                Key::LeftShift => "Left Shift",
+                Key::RightShift => "Right Shift",
+                Key::LeftControl => "Left Control",
+                Key::RightControl => "Right Control",
+                Key::LeftAlt => "Left Alt",
+                Key::RightAlt => "Right Alt",
+                Key::Pause => "Pause",
+                Key::CapsLock => "Caps Lock",
+                Key::Escape => "Escape",
+                Key::Space => "Space",
+                Key::PageUp => "Page Up",
+                Key::PageDown => "Page Down",
+                Key::End => "End",
+                Key::Home => "Home",
+                Key::LeftArrow => "Left",
+                Key::UpArrow => "Up",
+                Key::RightArrow => "Right",
+                Key::DownArrow => "Down",
+                Key::PrintScreen => "Print Screen",
+                Key::Insert => "Insert",
+                Key::Delete => "Delete",
+                Key::_0 => "0",
+                Key::_1 => "1",
+                Key::_2 => "2",
+                Key::_3 => "3",
+                Key::_4 => "4",
+                Key::_5 => "5",
+                Key::_6 => "6",
+                Key::_7 => "7",
+                Key::_8 => "8",
+                Key::_9 => "9",
+                Key::A => "A",
+                Key::B => "B",
+                Key::C => "C",
+                Key::D => "D",
+                Key::E => "E",
+                Key::F => "F",
+                Key::G => "G",
+                Key::H => "H",
+                Key::I => "I",
+                Key::J => "J",
+                Key::K => "K",
+                Key::L => "L",
+                Key::M => "M",
+                Key::N => "N",
+                Key::O => "O",
+                Key::P => "P",
+                Key::Q => "Q",
+                Key::R => "R",
+                Key::S => "S",
+                Key::T => "T",
+                Key::U => "U",
+                Key::V => "V",
+                Key::W => "W",
+                Key::X => "X",
+                Key::Y => "Y",
+                Key::Z => "Z",
+                Key::Windows => "Windows",
+                Key::Menu => "Menu",
+                Key::Numpad0 => "Numpad 0",
+                Key::Numpad1 => "Numpad 1",
+                Key::Numpad2 => "Numpad 2",
+                Key::Numpad3 => "Numpad 3",
+                Key::Numpad4 => "Numpad 4",
+                Key::Numpad5 => "Numpad 5",
+                Key::Numpad6 => "Numpad 6",
+                Key::Numpad7 => "Numpad 7",
+                Key::Numpad8 => "Numpad 8",
+                Key::Numpad9 => "Numpad 9",
+                Key::NumpadMultiply => "Numpad *",
+                Key::NumpadAdd => "Numpad +",
+                Key::NumpadSubtract => "Numpad -",
+                Key::NumpadDecimal => "Numpad .",
+                Key::NumpadDivide => "Numpad /",
+                Key::NumpadEnter => "Numpad Enter",
+                Key::F1 => "F1",
+                Key::F2 => "F2",
+                Key::F3 => "F3",
+                Key::F4 => "F4",
+                Key::F5 => "F5",
+                Key::F6 => "F6",
+                Key::F7 => "F7",
+                Key::F8 => "F8",
+                Key::F9 => "F9",
+                Key::F10 => "F10",
+                Key::F11 => "F11",
+                Key::F12 => "F12",
+                Key::Tilde => "`",
+                Key::Dash => "-",
+                Key::Equal => "=",
+                Key::NumLock => "Num Lock",
+                Key::ScrollLock => "Scroll Lock",
+                Key::Comma => ",",
+                Key::Period => ".",
+                Key::ForwardSlash => "/",
+                Key::BackSlash => "\\",
+                Key::LeftSquareBracket => "[",
+                Key::RightSquareBracket => "]",
+                Key::SemiColon => ";",
+                Key::Quote => "'",
+            }
+        )
+    }
+}
+
+impl ParseKeyError {
+    pub fn name(&self) -> &str {
+        &self.0
+    }
+}
+
+impl std::error::Error for ParseKeyError {}
+
+impl Display for ParseKeyError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Unknown key \"{}\"", self.0)
+    }
+}
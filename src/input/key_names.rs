@@ -0,0 +1,316 @@
+use crate::{Error, Key, Modifiers, MouseButton};
+use std::{borrow::Cow, collections::HashMap, fmt::Display, path::Path};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Platform {
+    Windows,
+    MacOS,
+    Linux,
+}
+
+pub trait KeyNames {
+    fn key_name(&self, key: Key) -> Cow<'_, str>;
+
+    // Called with a single modifier at a time
+    fn modifier_name(&self, modifier: Modifiers) -> Cow<'_, str>;
+
+    fn button_name(&self, button: MouseButton) -> Cow<'_, str> {
+        Cow::Owned(button.to_string())
+    }
+
+    fn separator(&self) -> &str {
+        "+"
+    }
+}
+
+pub struct EnglishKeyNames {
+    platform: Platform,
+}
+
+// Short symbols such as ⇧ and ⌃ for compact UI, falling back to English names
+pub struct GlyphKeyNames {
+    platform: Platform,
+    fallback: EnglishKeyNames,
+}
+
+pub struct TranslatedKeyNames<F: KeyNames> {
+    keys: HashMap<Key, String>,
+    modifiers: Vec<(Modifiers, String)>,
+    buttons: HashMap<MouseButton, String>,
+    fallback: F,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseKeyNamesError {
+    line: usize,
+    message: String,
+}
+
+impl Platform {
+    pub fn current() -> Platform {
+        if cfg!(target_os = "windows") {
+            Platform::Windows
+        } else if cfg!(any(target_os = "macos", target_os = "ios")) {
+            Platform::MacOS
+        } else {
+            Platform::Linux
+        }
+    }
+}
+
+impl EnglishKeyNames {
+    pub fn new(platform: Platform) -> Self {
+        EnglishKeyNames { platform }
+    }
+
+    pub fn platform(&self) -> Platform {
+        self.platform
+    }
+}
+
+impl KeyNames for EnglishKeyNames {
+    fn key_name(&self, key: Key) -> Cow<'_, str> {
+        Cow::Borrowed(match (self.platform, key) {
+            (Platform::Windows, Key::Windows) => "Windows",
+            (Platform::MacOS, Key::Windows) => "Command",
+            (Platform::Linux, Key::Windows) => "Super",
+            (Platform::MacOS, Key::LeftAlt) => "Left Option",
+            (Platform::MacOS, Key::RightAlt) => "Right Option",
+            (Platform::MacOS, Key::Backspace) => "Delete",
+            (Platform::MacOS, Key::Delete) => "Forward Delete",
+            (Platform::MacOS, Key::Enter) => "Return",
+            _ => return Cow::Owned(key.to_string()),
+        })
+    }
+
+    fn modifier_name(&self, modifier: Modifiers) -> Cow<'_, str> {
+        Cow::Borrowed(match (self.platform, modifier) {
+            (Platform::MacOS, Modifiers::CONTROL) => "Control",
+            (_, Modifiers::CONTROL) => "Ctrl",
+            (_, Modifiers::SHIFT) => "Shift",
+            (Platform::MacOS, Modifiers::ALT) => "Option",
+            (_, Modifiers::ALT) => "Alt",
+            (Platform::Windows, Modifiers::WINDOWS) => "Win",
+            (Platform::MacOS, Modifiers::WINDOWS) => "Command",
+            (Platform::Linux, Modifiers::WINDOWS) => "Super",
+            _ => return Cow::Owned(modifier.to_string()),
+        })
+    }
+}
+
+impl Default for EnglishKeyNames {
+    fn default() -> Self {
+        EnglishKeyNames::new(Platform::current())
+    }
+}
+
+impl GlyphKeyNames {
+    pub fn new(platform: Platform) -> Self {
+        GlyphKeyNames {
+            platform,
+            fallback: EnglishKeyNames::new(platform),
+        }
+    }
+
+    pub fn platform(&self) -> Platform {
+        self.platform
+    }
+}
+
+impl KeyNames for GlyphKeyNames {
+    fn key_name(&self, key: Key) -> Cow<'_, str> {
+        Cow::Borrowed(match key {
+            Key::Backspace => "⌫",
+            Key::Tab => "⇥",
+            Key::Enter | Key::NumpadEnter => "⏎",
+            Key::LeftShift | Key::RightShift => "⇧",
+            Key::LeftControl | Key::RightControl => "⌃",
+            Key::LeftAlt | Key::RightAlt if self.platform == Platform::MacOS => "⌥",
+            Key::CapsLock => "⇪",
+            Key::Escape => "⎋",
+            Key::Space => "␣",
+            Key::PageUp => "⇞",
+            Key::PageDown => "⇟",
+            Key::End => "↘",
+            Key::Home => "↖",
+            Key::LeftArrow => "←",
+            Key::UpArrow => "↑",
+            Key::RightArrow => "→",
+            Key::DownArrow => "↓",
+            Key::Delete => "⌦",
+            Key::Windows if self.platform == Platform::MacOS => "⌘",
+            Key::Windows if self.platform == Platform::Windows => "⊞",
+            _ => return self.fallback.key_name(key),
+        })
+    }
+
+    fn modifier_name(&self, modifier: Modifiers) -> Cow<'_, str> {
+        Cow::Borrowed(match (self.platform, modifier) {
+            (_, Modifiers::CONTROL) => "⌃",
+            (_, Modifiers::SHIFT) => "⇧",
+            (Platform::MacOS, Modifiers::ALT) => "⌥",
+            (Platform::MacOS, Modifiers::WINDOWS) => "⌘",
+            (Platform::Windows, Modifiers::WINDOWS) => "⊞",
+            _ => return self.fallback.modifier_name(modifier),
+        })
+    }
+
+    // macOS writes glyph shortcuts without a separator, e.g. ⌘⇧S
+    fn separator(&self) -> &str {
+        match self.platform {
+            Platform::MacOS => "",
+            _ => "+",
+        }
+    }
+}
+
+impl Default for GlyphKeyNames {
+    fn default() -> Self {
+        GlyphKeyNames::new(Platform::current())
+    }
+}
+
+impl<F: KeyNames> TranslatedKeyNames<F> {
+    pub fn new(fallback: F) -> Self {
+        TranslatedKeyNames {
+            keys: HashMap::new(),
+            modifiers: Vec::new(),
+            buttons: HashMap::new(),
+            fallback,
+        }
+    }
+
+    // One "name = translation" pair per line, names are anything Key, MouseButton or a
+    // modifier parses from. Lines starting with '#' are comments. Unprefixed names are tried as a
+    // modifier first, so names such as "Win" or "Shift" need a "key:" prefix to translate the key.
+    // "modifier:" and "button:" prefixes are accepted as well.
+    pub fn parse(table: &str, fallback: F) -> Result<Self, ParseKeyNamesError> {
+        let mut names = TranslatedKeyNames::new(fallback);
+
+        for (i, line) in table.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let error = |message: &str| ParseKeyNamesError {
+                line: i + 1,
+                message: message.to_owned(),
+            };
+
+            let (name, translation) = line.split_once('=').ok_or_else(|| error("Missing '='"))?;
+            let (name, translation) = (name.trim(), translation.trim().to_owned());
+
+            let unknown = |name: &str| error(&format!("Unknown key \"{}\"", name.trim()));
+
+            if let Some(key) = name.strip_prefix("key:") {
+                names.set_key_name(key.trim().parse().map_err(|_| unknown(key))?, translation);
+            } else if let Some(modifier) = name.strip_prefix("modifier:") {
+                let modifier = Modifiers::from_name(modifier).ok_or_else(|| unknown(modifier))?;
+                names.set_modifier_name(modifier, translation);
+            } else if let Some(button) = name.strip_prefix("button:") {
+                let button = button.trim().parse().map_err(|_| unknown(button))?;
+                names.set_button_name(button, translation);
+            } else if let Some(modifier) = Modifiers::from_name(name) {
+                names.set_modifier_name(modifier, translation);
+            } else if let Ok(key) = name.parse() {
+                names.set_key_name(key, translation);
+            } else if let Ok(button) = name.parse() {
+                names.set_button_name(button, translation);
+            } else {
+                return Err(unknown(name));
+            }
+        }
+
+        Ok(names)
+    }
+
+    pub fn load<P: AsRef<Path>>(path: P, fallback: F) -> Result<Self, Error> {
+        TranslatedKeyNames::parse(&std::fs::read_to_string(path)?, fallback)
+            .map_err(|error| Error::InvalidArgument(error.to_string()))
+    }
+
+    pub fn set_key_name(&mut self, key: Key, name: String) {
+        self.keys.insert(key, name);
+    }
+
+    pub fn set_modifier_name(&mut self, modifier: Modifiers, name: String) {
+        self.modifiers.retain(|(m, _)| *m != modifier);
+        self.modifiers.push((modifier, name));
+    }
+
+    pub fn set_button_name(&mut self, button: MouseButton, name: String) {
+        self.buttons.insert(button, name);
+    }
+
+    pub fn fallback(&self) -> &F {
+        &self.fallback
+    }
+}
+
+impl<F: KeyNames> KeyNames for TranslatedKeyNames<F> {
+    fn key_name(&self, key: Key) -> Cow<'_, str> {
+        match self.keys.get(&key) {
+            Some(name) => Cow::Borrowed(name),
+            None => self.fallback.key_name(key),
+        }
+    }
+
+    fn modifier_name(&self, modifier: Modifiers) -> Cow<'_, str> {
+        match self.modifiers.iter().find(|(m, _)| *m == modifier) {
+            Some((_, name)) => Cow::Borrowed(name),
+            None => self.fallback.modifier_name(modifier),
+        }
+    }
+
+    fn button_name(&self, button: MouseButton) -> Cow<'_, str> {
+        match self.buttons.get(&button) {
+            Some(name) => Cow::Borrowed(name),
+            None => self.fallback.button_name(button),
+        }
+    }
+
+    fn separator(&self) -> &str {
+        self.fallback.separator()
+    }
+}
+
+impl ParseKeyNamesError {
+    pub fn line(&self) -> usize {
+        self.line
+    }
+}
+
+impl std::error::Error for ParseKeyNamesError {}
+
+impl Display for ParseKeyNamesError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Line {}: {}", self.line, self.message)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn prefixed_names() {
+        let names = TranslatedKeyNames::parse(
+            "Win = Modifier\nkey:Win = Key\nmodifier:Shift = Umschalt\nbutton:Left = Button",
+            EnglishKeyNames::new(Platform::Windows),
+        )
+        .unwrap();
+        assert_eq!(names.modifier_name(Modifiers::WINDOWS), "Modifier");
+        assert_eq!(names.key_name(Key::Windows), "Key");
+        assert_eq!(names.modifier_name(Modifiers::SHIFT), "Umschalt");
+        assert_eq!(names.button_name(MouseButton::Primary), "Button");
+
+        let error = TranslatedKeyNames::parse(
+            "key:Win = Key\nkey:Nope = Nope",
+            EnglishKeyNames::new(Platform::Windows),
+        )
+        .err()
+        .unwrap();
+        assert_eq!(error.to_string(), "Line 2: Unknown key \"Nope\"");
+    }
+}
@@ -0,0 +1,47 @@
+mod encoding;
+mod key;
+mod key_names;
+mod mouse_button;
+mod press_tracker;
+mod recording;
+mod scancode;
+mod sequence;
+mod shortcut;
+mod snapshot;
+mod touch;
+
+pub use key::{Key, ParseKeyError};
+pub use key_names::{
+    EnglishKeyNames, GlyphKeyNames, KeyNames, ParseKeyNamesError, Platform, TranslatedKeyNames,
+};
+pub use mouse_button::{MouseButton, ParseMouseButtonError};
+pub use press_tracker::{ClickConfig, PressTracker, RepeatConfig};
+pub use recording::{
+    InputPlayback, InputRecorder, RecordedEvent, RecordedInput, Recording, RecordingDifference,
+};
+pub use scancode::{KeyboardLayout, ScanCode};
+pub use sequence::{Sequence, SequenceInput, SequenceMatcher, SequenceStep};
+pub use shortcut::{KeyStroke, Modifiers, ParseShortcutError, Shortcut, ShortcutMatcher};
+pub use snapshot::{InputPredictor, InputSnapshot, InputTransport, LoopbackTransport};
+pub use touch::{
+    Gesture, GestureConfig, GestureRecognizer, SyntheticTouchSource, TouchPhase, TouchPoint,
+};
+
+pub trait Input {
+    fn new() -> Self;
+
+    fn key_down(&mut self, key: Key);
+    fn key_up(&mut self, key: Key);
+    // Auto-repeat from the OS while a key is held, key_down is only sent for the first press
+    fn key_repeat(&mut self, _key: Key) {}
+    fn mouse_down(&mut self, key: MouseButton);
+    fn mouse_up(&mut self, key: MouseButton);
+    fn update_mouse_position(&mut self, position: (isize, isize));
+    fn set_mouse_lock(&mut self, state: bool);
+    fn frame_reset(&mut self);
+
+    // Backends without touch support never call this
+    fn touch(&mut self, _touch: TouchPoint) {}
+
+    fn is_mouse_locked(&self) -> bool;
+}
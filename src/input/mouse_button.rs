@@ -0,0 +1,76 @@
+use std::{fmt::Display, str::FromStr};
+
+#[repr(u8)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MouseButton {
+    Primary,
+    Secondary,
+    Middle,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseMouseButtonError(String);
+
+impl MouseButton {
+    pub const ALL: [MouseButton; 3] = [
+        MouseButton::Primary,
+        MouseButton::Secondary,
+        MouseButton::Middle,
+    ];
+
+    pub fn iter() -> impl Iterator<Item = MouseButton> {
+        MouseButton::ALL.into_iter()
+    }
+}
+
+impl TryFrom<u8> for MouseButton {
+    type Error = u8;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        MouseButton::ALL
+            .into_iter()
+            .find(|item| *item as u8 == value)
+            .ok_or(value)
+    }
+}
+
+impl FromStr for MouseButton {
+    type Err = ParseMouseButtonError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "primary" | "left" | "lmb" | "mouse 1" | "mouse1" => Ok(MouseButton::Primary),
+            "secondary" | "right" | "rmb" | "mouse 2" | "mouse2" => Ok(MouseButton::Secondary),
+            "middle" | "wheel" | "mmb" | "mouse 3" | "mouse3" => Ok(MouseButton::Middle),
+            _ => Err(ParseMouseButtonError(s.to_owned())),
+        }
+    }
+}
+
+impl Display for MouseButton {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                MouseButton::Primary => "Primary",
+                MouseButton::Secondary => "Secondary",
+                MouseButton::Middle => "Middle",
+            }
+        )
+    }
+}
+
+impl ParseMouseButtonError {
+    pub fn name(&self) -> &str {
+        &self.0
+    }
+}
+
+impl std::error::Error for ParseMouseButtonError {}
+
+impl Display for ParseMouseButtonError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Unknown mouse button \"{}\"", self.0)
+    }
+}
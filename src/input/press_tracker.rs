@@ -0,0 +1,239 @@
+use crate::{Key, MouseButton, Vector2};
+use std::{
+    collections::HashMap,
+    time::{Duration, Instant},
+};
+
+#[derive(Debug, Clone, Copy)]
+pub struct RepeatConfig {
+    pub delay: Duration,
+    pub rate: Duration,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct ClickConfig {
+    pub interval: Duration,
+    pub distance: f32,
+    pub max_count: u32,
+}
+
+pub struct PressTracker {
+    repeat: RepeatConfig,
+    click: ClickConfig,
+    keys: HashMap<Key, HeldKey>,
+    buttons: HashMap<MouseButton, Instant>,
+    key_holds: HashMap<Key, Duration>,
+    button_holds: HashMap<MouseButton, Duration>,
+    last_click: Option<Click>,
+}
+
+struct HeldKey {
+    pressed: Instant,
+    next_repeat: Instant,
+}
+
+struct Click {
+    button: MouseButton,
+    time: Instant,
+    position: Vector2,
+    count: u32,
+}
+
+impl PressTracker {
+    pub fn new(repeat: RepeatConfig, click: ClickConfig) -> Self {
+        PressTracker {
+            repeat,
+            click,
+            keys: HashMap::new(),
+            buttons: HashMap::new(),
+            key_holds: HashMap::new(),
+            button_holds: HashMap::new(),
+            last_click: None,
+        }
+    }
+
+    pub fn repeat_config(&self) -> &RepeatConfig {
+        &self.repeat
+    }
+
+    pub fn set_repeat_config(&mut self, repeat: RepeatConfig) {
+        self.repeat = repeat;
+    }
+
+    pub fn click_config(&self) -> &ClickConfig {
+        &self.click
+    }
+
+    pub fn set_click_config(&mut self, click: ClickConfig) {
+        self.click = click;
+    }
+
+    // Presses of a key that is already held are ignored, so OS repeats can be fed here safely
+    pub fn key_down(&mut self, key: Key, time: Instant) {
+        self.keys.entry(key).or_insert(HeldKey {
+            pressed: time,
+            next_repeat: time + self.repeat.delay,
+        });
+    }
+
+    // Returns how long the key was held
+    pub fn key_up(&mut self, key: Key, time: Instant) -> Option<Duration> {
+        let held = self.keys.remove(&key)?;
+        let duration = time.saturating_duration_since(held.pressed);
+        self.key_holds.insert(key, duration);
+        Some(duration)
+    }
+
+    pub fn is_key_held(&self, key: Key) -> bool {
+        self.keys.contains_key(&key)
+    }
+
+    pub fn key_pressed_at(&self, key: Key) -> Option<Instant> {
+        self.keys.get(&key).map(|held| held.pressed)
+    }
+
+    pub fn key_hold_duration(&self, key: Key, time: Instant) -> Option<Duration> {
+        self.key_pressed_at(key)
+            .map(|pressed| time.saturating_duration_since(pressed))
+    }
+
+    // Duration of the most recent completed press
+    pub fn last_key_hold(&self, key: Key) -> Option<Duration> {
+        self.key_holds.get(&key).copied()
+    }
+
+    // Generates repeats for held keys using the configured delay and rate instead of the OS settings
+    pub fn repeats(&mut self, time: Instant) -> Vec<Key> {
+        let rate = self.repeat.rate.max(Duration::from_millis(1));
+        let mut repeats = Vec::new();
+        for (key, held) in &mut self.keys {
+            // After a stall such as a breakpoint or a window drag the schedule restarts with a
+            // single repeat instead of catching up
+            if time.saturating_duration_since(held.next_repeat) > rate {
+                repeats.push(*key);
+                held.next_repeat = time + rate;
+            }
+
+            while held.next_repeat <= time {
+                repeats.push(*key);
+                held.next_repeat += rate;
+            }
+        }
+        repeats
+    }
+
+    // Returns the click count, 2 for a double click and 3 for a triple click
+    pub fn mouse_down(&mut self, button: MouseButton, position: Vector2, time: Instant) -> u32 {
+        self.buttons.entry(button).or_insert(time);
+
+        let count = match &self.last_click {
+            Some(click)
+                if click.button == button
+                    && time.saturating_duration_since(click.time) <= self.click.interval
+                    && (position - click.position).magnitude() <= self.click.distance
+                    && click.count < self.click.max_count =>
+            {
+                click.count + 1
+            }
+            _ => 1,
+        };
+
+        self.last_click = Some(Click {
+            button,
+            time,
+            position,
+            count,
+        });
+        count
+    }
+
+    // Returns how long the button was held
+    pub fn mouse_up(&mut self, button: MouseButton, time: Instant) -> Option<Duration> {
+        let pressed = self.buttons.remove(&button)?;
+        let duration = time.saturating_duration_since(pressed);
+        self.button_holds.insert(button, duration);
+        Some(duration)
+    }
+
+    pub fn is_button_held(&self, button: MouseButton) -> bool {
+        self.buttons.contains_key(&button)
+    }
+
+    pub fn button_pressed_at(&self, button: MouseButton) -> Option<Instant> {
+        self.buttons.get(&button).copied()
+    }
+
+    pub fn button_hold_duration(&self, button: MouseButton, time: Instant) -> Option<Duration> {
+        self.button_pressed_at(button)
+            .map(|pressed| time.saturating_duration_since(pressed))
+    }
+
+    pub fn last_button_hold(&self, button: MouseButton) -> Option<Duration> {
+        self.button_holds.get(&button).copied()
+    }
+
+    // Releases everything, for example when the window loses focus
+    pub fn reset(&mut self) {
+        self.keys.clear();
+        self.buttons.clear();
+        self.last_click = None;
+    }
+}
+
+impl Default for PressTracker {
+    fn default() -> Self {
+        PressTracker::new(RepeatConfig::default(), ClickConfig::default())
+    }
+}
+
+impl Default for RepeatConfig {
+    fn default() -> Self {
+        RepeatConfig {
+            delay: Duration::from_millis(500),
+            rate: Duration::from_millis(33),
+        }
+    }
+}
+
+impl Default for ClickConfig {
+    fn default() -> Self {
+        ClickConfig {
+            interval: Duration::from_millis(500),
+            distance: 4.0,
+            max_count: 3,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn repeats_after_long_gap() {
+        let mut tracker = PressTracker::new(
+            RepeatConfig {
+                delay: Duration::from_millis(500),
+                rate: Duration::from_millis(30),
+            },
+            ClickConfig::default(),
+        );
+        let time = Instant::now();
+        tracker.key_down(Key::A, time);
+
+        assert!(tracker
+            .repeats(time + Duration::from_millis(499))
+            .is_empty());
+        assert_eq!(tracker.repeats(time + Duration::from_millis(525)), [Key::A]);
+        assert_eq!(
+            tracker.repeats(time + Duration::from_millis(560)),
+            [Key::A, Key::A]
+        );
+
+        // A 60 second stall gives one repeat and the schedule continues from there
+        let time = time + Duration::from_secs(60);
+        assert_eq!(tracker.repeats(time), [Key::A]);
+        assert!(tracker.repeats(time + Duration::from_millis(29)).is_empty());
+        assert_eq!(tracker.repeats(time + Duration::from_millis(30)), [Key::A]);
+    }
+}
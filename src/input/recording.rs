@@ -0,0 +1,581 @@
+use super::encoding::{
+    invalid_data, read_button, read_f32, read_key, read_u8, read_varint, unzigzag, write_varint,
+    zigzag,
+};
+use crate::{Input, Key, MouseButton, TouchPhase, TouchPoint, Vector2};
+use std::{
+    fmt::Display,
+    fs::File,
+    io::{BufReader, BufWriter, Read, Write},
+    path::Path,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RecordedInput {
+    KeyDown(Key),
+    KeyUp(Key),
+    MouseDown(MouseButton),
+    MouseUp(MouseButton),
+    MousePosition(isize, isize),
+    MouseLock(bool),
+    FrameReset,
+    Touch(TouchPoint),
+    KeyRepeat(Key),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RecordedEvent {
+    frame: u64,
+    input: RecordedInput,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Recording {
+    events: Vec<RecordedEvent>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RecordingDifference {
+    frame: u64,
+    left: Vec<RecordedInput>,
+    right: Vec<RecordedInput>,
+}
+
+pub struct InputRecorder<I: Input> {
+    input: I,
+    recording: Recording,
+    frame: u64,
+    paused: bool,
+}
+
+pub struct InputPlayback {
+    recording: Recording,
+    position: usize,
+    frame: u64,
+}
+
+const MAGIC: &[u8; 4] = b"AIRC";
+const VERSION: u8 = 1;
+
+const TAG_KEY_DOWN: u8 = 0;
+const TAG_KEY_UP: u8 = 1;
+const TAG_MOUSE_DOWN: u8 = 2;
+const TAG_MOUSE_UP: u8 = 3;
+const TAG_MOUSE_POSITION: u8 = 4;
+const TAG_MOUSE_LOCK: u8 = 5;
+const TAG_FRAME_RESET: u8 = 6;
+const TAG_TOUCH: u8 = 7;
+const TAG_KEY_REPEAT: u8 = 8;
+
+impl RecordedInput {
+    pub fn apply<I: Input>(&self, input: &mut I) {
+        match *self {
+            RecordedInput::KeyDown(key) => input.key_down(key),
+            RecordedInput::KeyUp(key) => input.key_up(key),
+            RecordedInput::MouseDown(button) => input.mouse_down(button),
+            RecordedInput::MouseUp(button) => input.mouse_up(button),
+            RecordedInput::MousePosition(x, y) => input.update_mouse_position((x, y)),
+            RecordedInput::MouseLock(state) => input.set_mouse_lock(state),
+            RecordedInput::FrameReset => input.frame_reset(),
+            RecordedInput::Touch(touch) => input.touch(touch),
+            RecordedInput::KeyRepeat(key) => input.key_repeat(key),
+        }
+    }
+}
+
+impl RecordedEvent {
+    pub fn new(frame: u64, input: RecordedInput) -> Self {
+        RecordedEvent { frame, input }
+    }
+
+    pub fn frame(&self) -> u64 {
+        self.frame
+    }
+
+    pub fn input(&self) -> RecordedInput {
+        self.input
+    }
+}
+
+impl Recording {
+    pub fn new() -> Self {
+        Recording { events: Vec::new() }
+    }
+
+    pub fn events(&self) -> &[RecordedEvent] {
+        &self.events
+    }
+
+    pub fn frame_count(&self) -> u64 {
+        self.events.last().map(|event| event.frame + 1).unwrap_or(0)
+    }
+
+    pub fn frame_events(&self, frame: u64) -> impl Iterator<Item = RecordedInput> + '_ {
+        let start = self.events.partition_point(|event| event.frame < frame);
+        self.events[start..]
+            .iter()
+            .take_while(move |event| event.frame == frame)
+            .map(|event| event.input)
+    }
+
+    pub fn push(&mut self, frame: u64, input: RecordedInput) {
+        assert!(frame >= self.frame_count().saturating_sub(1));
+        self.events.push(RecordedEvent::new(frame, input));
+    }
+
+    pub fn clear(&mut self) {
+        self.events.clear();
+    }
+
+    // Events are sorted by frame so both recordings are walked once side by side
+    pub fn diff(&self, other: &Recording) -> Vec<RecordingDifference> {
+        let mut differences = Vec::new();
+        let mut left = self.events.as_slice();
+        let mut right = other.events.as_slice();
+        loop {
+            let frame = match (left.first(), right.first()) {
+                (Some(l), Some(r)) => l.frame.min(r.frame),
+                (Some(event), None) | (None, Some(event)) => event.frame,
+                (None, None) => break,
+            };
+
+            let (left_events, left_rest) = split_frame(left, frame);
+            let (right_events, right_rest) = split_frame(right, frame);
+            left = left_rest;
+            right = right_rest;
+
+            if !left_events
+                .iter()
+                .map(RecordedEvent::input)
+                .eq(right_events.iter().map(RecordedEvent::input))
+            {
+                differences.push(RecordingDifference {
+                    frame,
+                    left: left_events.iter().map(RecordedEvent::input).collect(),
+                    right: right_events.iter().map(RecordedEvent::input).collect(),
+                });
+            }
+        }
+        differences
+    }
+
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> std::io::Result<()> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        self.write(&mut writer)?;
+        writer.flush()
+    }
+
+    pub fn load<P: AsRef<Path>>(path: P) -> std::io::Result<Self> {
+        Recording::read(&mut BufReader::new(File::open(path)?))
+    }
+
+    // Frames are stored as a varint delta from the previous event
+    pub fn write<W: Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        writer.write_all(MAGIC)?;
+        writer.write_all(&[VERSION])?;
+        write_varint(writer, self.events.len() as u64)?;
+
+        let mut frame = 0;
+        for event in &self.events {
+            write_varint(writer, event.frame - frame)?;
+            frame = event.frame;
+
+            match event.input {
+                RecordedInput::KeyDown(key) => writer.write_all(&[TAG_KEY_DOWN, key as u8])?,
+                RecordedInput::KeyUp(key) => writer.write_all(&[TAG_KEY_UP, key as u8])?,
+                RecordedInput::MouseDown(button) => {
+                    writer.write_all(&[TAG_MOUSE_DOWN, button as u8])?
+                }
+                RecordedInput::MouseUp(button) => {
+                    writer.write_all(&[TAG_MOUSE_UP, button as u8])?
+                }
+                RecordedInput::MousePosition(x, y) => {
+                    writer.write_all(&[TAG_MOUSE_POSITION])?;
+                    write_varint(writer, zigzag(x))?;
+                    write_varint(writer, zigzag(y))?;
+                }
+                RecordedInput::MouseLock(state) => {
+                    writer.write_all(&[TAG_MOUSE_LOCK, state as u8])?
+                }
+                RecordedInput::FrameReset => writer.write_all(&[TAG_FRAME_RESET])?,
+                RecordedInput::Touch(touch) => {
+                    writer.write_all(&[TAG_TOUCH])?;
+                    write_varint(writer, touch.id())?;
+                    writer.write_all(&touch.position().x().to_le_bytes())?;
+                    writer.write_all(&touch.position().y().to_le_bytes())?;
+                    writer.write_all(&touch.pressure().to_le_bytes())?;
+                    writer.write_all(&[touch.phase() as u8])?;
+                }
+                RecordedInput::KeyRepeat(key) => writer.write_all(&[TAG_KEY_REPEAT, key as u8])?,
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn read<R: Read>(reader: &mut R) -> std::io::Result<Self> {
+        let mut magic = [0; 4];
+        reader.read_exact(&mut magic)?;
+        if &magic != MAGIC {
+            return Err(invalid_data("Invalid recording magic"));
+        }
+
+        let version = read_u8(reader)?;
+        if version != VERSION {
+            return Err(invalid_data("Unsupported recording version"));
+        }
+
+        let count = read_varint(reader)?;
+        let mut events = Vec::new();
+        let mut frame: u64 = 0;
+        for _ in 0..count {
+            frame = frame
+                .checked_add(read_varint(reader)?)
+                .ok_or_else(|| invalid_data("Invalid recording frame"))?;
+
+            let input = match read_u8(reader)? {
+                TAG_KEY_DOWN => RecordedInput::KeyDown(read_key(reader)?),
+                TAG_KEY_UP => RecordedInput::KeyUp(read_key(reader)?),
+                TAG_MOUSE_DOWN => RecordedInput::MouseDown(read_button(reader)?),
+                TAG_MOUSE_UP => RecordedInput::MouseUp(read_button(reader)?),
+                TAG_MOUSE_POSITION => RecordedInput::MousePosition(
+                    unzigzag(read_varint(reader)?),
+                    unzigzag(read_varint(reader)?),
+                ),
+                TAG_MOUSE_LOCK => RecordedInput::MouseLock(read_u8(reader)? != 0),
+                TAG_FRAME_RESET => RecordedInput::FrameReset,
+                TAG_TOUCH => RecordedInput::Touch(read_touch(reader)?),
+                TAG_KEY_REPEAT => RecordedInput::KeyRepeat(read_key(reader)?),
+                _ => return Err(invalid_data("Invalid recording event")),
+            };
+
+            events.push(RecordedEvent::new(frame, input));
+        }
+
+        Ok(Recording { events })
+    }
+}
+
+impl RecordingDifference {
+    pub fn frame(&self) -> u64 {
+        self.frame
+    }
+
+    pub fn left(&self) -> &[RecordedInput] {
+        &self.left
+    }
+
+    pub fn right(&self) -> &[RecordedInput] {
+        &self.right
+    }
+}
+
+impl<I: Input> InputRecorder<I> {
+    pub fn with_input(input: I) -> Self {
+        InputRecorder {
+            input,
+            recording: Recording::new(),
+            frame: 0,
+            paused: false,
+        }
+    }
+
+    pub fn input(&self) -> &I {
+        &self.input
+    }
+
+    pub fn input_mut(&mut self) -> &mut I {
+        &mut self.input
+    }
+
+    pub fn recording(&self) -> &Recording {
+        &self.recording
+    }
+
+    pub fn take_recording(&mut self) -> Recording {
+        self.frame = 0;
+        std::mem::take(&mut self.recording)
+    }
+
+    pub fn frame(&self) -> u64 {
+        self.frame
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    pub fn set_paused(&mut self, paused: bool) {
+        self.paused = paused;
+    }
+
+    pub fn into_inner(self) -> (I, Recording) {
+        (self.input, self.recording)
+    }
+
+    fn record(&mut self, input: RecordedInput) {
+        if !self.paused {
+            self.recording.push(self.frame, input);
+        }
+    }
+}
+
+impl<I: Input> Input for InputRecorder<I> {
+    fn new() -> Self {
+        InputRecorder::with_input(I::new())
+    }
+
+    fn key_down(&mut self, key: Key) {
+        self.record(RecordedInput::KeyDown(key));
+        self.input.key_down(key);
+    }
+
+    fn key_up(&mut self, key: Key) {
+        self.record(RecordedInput::KeyUp(key));
+        self.input.key_up(key);
+    }
+
+    fn key_repeat(&mut self, key: Key) {
+        self.record(RecordedInput::KeyRepeat(key));
+        self.input.key_repeat(key);
+    }
+
+    fn mouse_down(&mut self, key: MouseButton) {
+        self.record(RecordedInput::MouseDown(key));
+        self.input.mouse_down(key);
+    }
+
+    fn mouse_up(&mut self, key: MouseButton) {
+        self.record(RecordedInput::MouseUp(key));
+        self.input.mouse_up(key);
+    }
+
+    fn update_mouse_position(&mut self, position: (isize, isize)) {
+        self.record(RecordedInput::MousePosition(position.0, position.1));
+        self.input.update_mouse_position(position);
+    }
+
+    fn set_mouse_lock(&mut self, state: bool) {
+        self.record(RecordedInput::MouseLock(state));
+        self.input.set_mouse_lock(state);
+    }
+
+    fn frame_reset(&mut self) {
+        self.record(RecordedInput::FrameReset);
+        self.input.frame_reset();
+        if !self.paused {
+            self.frame += 1;
+        }
+    }
+
+    fn touch(&mut self, touch: TouchPoint) {
+        self.record(RecordedInput::Touch(touch));
+        self.input.touch(touch);
+    }
+
+    fn is_mouse_locked(&self) -> bool {
+        self.input.is_mouse_locked()
+    }
+}
+
+impl InputPlayback {
+    pub fn new(recording: Recording) -> Self {
+        InputPlayback {
+            recording,
+            position: 0,
+            frame: 0,
+        }
+    }
+
+    pub fn recording(&self) -> &Recording {
+        &self.recording
+    }
+
+    pub fn frame(&self) -> u64 {
+        self.frame
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.position >= self.recording.events.len()
+    }
+
+    // Applies every event of the current frame, returns false once the recording is exhausted
+    pub fn play_frame<I: Input>(&mut self, input: &mut I) -> bool {
+        if self.is_finished() {
+            return false;
+        }
+
+        while let Some(event) = self.recording.events.get(self.position) {
+            if event.frame != self.frame {
+                break;
+            }
+
+            event.input.apply(input);
+            self.position += 1;
+        }
+
+        self.frame += 1;
+        true
+    }
+
+    // Inputs have no way to be rewound, so seeking replays into a fresh input
+    pub fn seek<I: Input>(&mut self, frame: u64) -> I {
+        let mut input = I::new();
+        self.rewind();
+        while self.frame < frame && self.play_frame(&mut input) {}
+        input
+    }
+
+    pub fn rewind(&mut self) {
+        self.position = 0;
+        self.frame = 0;
+    }
+}
+
+impl Display for RecordedInput {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RecordedInput::KeyDown(key) => write!(f, "Key down {}", key),
+            RecordedInput::KeyUp(key) => write!(f, "Key up {}", key),
+            RecordedInput::MouseDown(button) => write!(f, "Mouse down {}", button),
+            RecordedInput::MouseUp(button) => write!(f, "Mouse up {}", button),
+            RecordedInput::MousePosition(x, y) => write!(f, "Mouse position ({}, {})", x, y),
+            RecordedInput::MouseLock(state) => write!(f, "Mouse lock {}", state),
+            RecordedInput::FrameReset => write!(f, "Frame reset"),
+            RecordedInput::Touch(touch) => write!(
+                f,
+                "Touch {} {:?} {} ({})",
+                touch.id(),
+                touch.phase(),
+                touch.position(),
+                touch.pressure()
+            ),
+            RecordedInput::KeyRepeat(key) => write!(f, "Key repeat {}", key),
+        }
+    }
+}
+
+impl Display for RecordingDifference {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "Frame {}:", self.frame)?;
+        for input in &self.left {
+            writeln!(f, "  - {}", input)?;
+        }
+        for input in &self.right {
+            writeln!(f, "  + {}", input)?;
+        }
+        Ok(())
+    }
+}
+
+fn read_touch<R: Read>(reader: &mut R) -> std::io::Result<TouchPoint> {
+    let id = read_varint(reader)?;
+    let position = Vector2::new(read_f32(reader)?, read_f32(reader)?);
+    let pressure = read_f32(reader)?;
+    let phase = match read_u8(reader)? {
+        0 => TouchPhase::Started,
+        1 => TouchPhase::Moved,
+        2 => TouchPhase::Ended,
+        3 => TouchPhase::Cancelled,
+        _ => return Err(invalid_data("Invalid touch phase")),
+    };
+    Ok(TouchPoint::new(id, position, pressure, phase))
+}
+
+// Splits off the leading events that happen on `frame`
+fn split_frame(events: &[RecordedEvent], frame: u64) -> (&[RecordedEvent], &[RecordedEvent]) {
+    let len = events
+        .iter()
+        .take_while(|event| event.frame == frame)
+        .count();
+    events.split_at(len)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn every_event() -> Recording {
+        let touch = TouchPoint::new(
+            u64::MAX,
+            Vector2::new(-12.5, 300.25),
+            0.75,
+            TouchPhase::Cancelled,
+        );
+
+        let mut recording = Recording::new();
+        recording.push(0, RecordedInput::KeyDown(Key::A));
+        recording.push(0, RecordedInput::MouseDown(MouseButton::Secondary));
+        recording.push(3, RecordedInput::KeyRepeat(Key::A));
+        recording.push(3, RecordedInput::MousePosition(isize::MIN, isize::MAX));
+        recording.push(200, RecordedInput::MousePosition(-1, 0));
+        recording.push(200, RecordedInput::MouseLock(true));
+        recording.push(70_000, RecordedInput::Touch(touch));
+        recording.push(70_000, RecordedInput::KeyUp(Key::A));
+        recording.push(70_001, RecordedInput::MouseUp(MouseButton::Secondary));
+        recording.push(u64::MAX - 1, RecordedInput::FrameReset);
+        recording
+    }
+
+    fn encode(recording: &Recording) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        recording.write(&mut bytes).unwrap();
+        bytes
+    }
+
+    #[test]
+    fn round_trip() {
+        let recording = every_event();
+        let bytes = encode(&recording);
+        assert_eq!(Recording::read(&mut bytes.as_slice()).unwrap(), recording);
+
+        let empty = encode(&Recording::new());
+        assert_eq!(
+            Recording::read(&mut empty.as_slice()).unwrap(),
+            Recording::new()
+        );
+    }
+
+    #[test]
+    fn truncated() {
+        let bytes = encode(&every_event());
+        for length in 0..bytes.len() {
+            let error = Recording::read(&mut &bytes[..length]).unwrap_err();
+            assert_eq!(error.kind(), std::io::ErrorKind::UnexpectedEof);
+        }
+    }
+
+    #[test]
+    fn invalid_recordings() {
+        let invalid = |bytes: &[u8]| {
+            let mut recording = MAGIC.to_vec();
+            recording.push(VERSION);
+            recording.extend_from_slice(bytes);
+            Recording::read(&mut recording.as_slice())
+                .unwrap_err()
+                .kind()
+        };
+
+        // Unknown tag, invalid key and an overflowing frame delta
+        assert_eq!(invalid(&[1, 0, 9]), std::io::ErrorKind::InvalidData);
+        assert_eq!(
+            invalid(&[1, 0, TAG_KEY_DOWN, 0xFF]),
+            std::io::ErrorKind::InvalidData
+        );
+        let mut overflow = vec![2];
+        write_varint(&mut overflow, u64::MAX).unwrap();
+        overflow.push(TAG_FRAME_RESET);
+        overflow.push(1);
+        overflow.push(TAG_FRAME_RESET);
+        assert_eq!(invalid(&overflow), std::io::ErrorKind::InvalidData);
+
+        let mut wrong_magic = encode(&every_event());
+        wrong_magic[0] = b'X';
+        assert_eq!(
+            Recording::read(&mut wrong_magic.as_slice())
+                .unwrap_err()
+                .kind(),
+            std::io::ErrorKind::InvalidData
+        );
+    }
+}
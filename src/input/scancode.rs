@@ -0,0 +1,281 @@
+use crate::Key;
+use std::{fmt::Display, num::ParseIntError, str::FromStr};
+
+// Physical key position as a PC set 1 scancode, extended codes are prefixed with 0xE0
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct ScanCode(u16);
+
+#[derive(Debug, Clone)]
+pub struct KeyboardLayout {
+    name: String,
+    table: Vec<(ScanCode, Key)>,
+}
+
+const QWERTY: [(u16, Key); 103] = [
+    (0x01, Key::Escape),
+    (0x02, Key::_1),
+    (0x03, Key::_2),
+    (0x04, Key::_3),
+    (0x05, Key::_4),
+    (0x06, Key::_5),
+    (0x07, Key::_6),
+    (0x08, Key::_7),
+    (0x09, Key::_8),
+    (0x0A, Key::_9),
+    (0x0B, Key::_0),
+    (0x0C, Key::Dash),
+    (0x0D, Key::Equal),
+    (0x0E, Key::Backspace),
+    (0x0F, Key::Tab),
+    (0x10, Key::Q),
+    (0x11, Key::W),
+    (0x12, Key::E),
+    (0x13, Key::R),
+    (0x14, Key::T),
+    (0x15, Key::Y),
+    (0x16, Key::U),
+    (0x17, Key::I),
+    (0x18, Key::O),
+    (0x19, Key::P),
+    (0x1A, Key::LeftSquareBracket),
+    (0x1B, Key::RightSquareBracket),
+    (0x1C, Key::Enter),
+    (0x1D, Key::LeftControl),
+    (0x1E, Key::A),
+    (0x1F, Key::S),
+    (0x20, Key::D),
+    (0x21, Key::F),
+    (0x22, Key::G),
+    (0x23, Key::H),
+    (0x24, Key::J),
+    (0x25, Key::K),
+    (0x26, Key::L),
+    (0x27, Key::SemiColon),
+    (0x28, Key::Quote),
+    (0x29, Key::Tilde),
+    (0x2A, Key::LeftShift),
+    (0x2B, Key::BackSlash),
+    (0x2C, Key::Z),
+    (0x2D, Key::X),
+    (0x2E, Key::C),
+    (0x2F, Key::V),
+    (0x30, Key::B),
+    (0x31, Key::N),
+    (0x32, Key::M),
+    (0x33, Key::Comma),
+    (0x34, Key::Period),
+    (0x35, Key::ForwardSlash),
+    (0x36, Key::RightShift),
+    (0x37, Key::NumpadMultiply),
+    (0x38, Key::LeftAlt),
+    (0x39, Key::Space),
+    (0x3A, Key::CapsLock),
+    (0x3B, Key::F1),
+    (0x3C, Key::F2),
+    (0x3D, Key::F3),
+    (0x3E, Key::F4),
+    (0x3F, Key::F5),
+    (0x40, Key::F6),
+    (0x41, Key::F7),
+    (0x42, Key::F8),
+    (0x43, Key::F9),
+    (0x44, Key::F10),
+    (0x45, Key::NumLock),
+    (0x46, Key::ScrollLock),
+    (0x47, Key::Numpad7),
+    (0x48, Key::Numpad8),
+    (0x49, Key::Numpad9),
+    (0x4A, Key::NumpadSubtract),
+    (0x4B, Key::Numpad4),
+    (0x4C, Key::Numpad5),
+    (0x4D, Key::Numpad6),
+    (0x4E, Key::NumpadAdd),
+    (0x4F, Key::Numpad1),
+    (0x50, Key::Numpad2),
+    (0x51, Key::Numpad3),
+    (0x52, Key::Numpad0),
+    (0x53, Key::NumpadDecimal),
+    (0x57, Key::F11),
+    (0x58, Key::F12),
+    (0xE01C, Key::NumpadEnter),
+    (0xE01D, Key::RightControl),
+    (0xE035, Key::NumpadDivide),
+    (0xE037, Key::PrintScreen),
+    (0xE038, Key::RightAlt),
+    (0xE045, Key::Pause),
+    (0xE047, Key::Home),
+    (0xE048, Key::UpArrow),
+    (0xE049, Key::PageUp),
+    (0xE04B, Key::LeftArrow),
+    (0xE04D, Key::RightArrow),
+    (0xE04F, Key::End),
+    (0xE050, Key::DownArrow),
+    (0xE051, Key::PageDown),
+    (0xE052, Key::Insert),
+    (0xE053, Key::Delete),
+    (0xE05B, Key::Windows),
+    (0xE05D, Key::Menu),
+];
+
+// Keys that have no equivalent on a layout keep their QWERTY position
+const AZERTY: [(u16, Key); 7] = [
+    (0x10, Key::A),
+    (0x11, Key::Z),
+    (0x1E, Key::Q),
+    (0x27, Key::M),
+    (0x2C, Key::W),
+    (0x32, Key::Comma),
+    (0x33, Key::SemiColon),
+];
+
+const DVORAK: [(u16, Key); 35] = [
+    (0x0C, Key::LeftSquareBracket),
+    (0x0D, Key::RightSquareBracket),
+    (0x10, Key::Quote),
+    (0x11, Key::Comma),
+    (0x12, Key::Period),
+    (0x13, Key::P),
+    (0x14, Key::Y),
+    (0x15, Key::F),
+    (0x16, Key::G),
+    (0x17, Key::C),
+    (0x18, Key::R),
+    (0x19, Key::L),
+    (0x1A, Key::ForwardSlash),
+    (0x1B, Key::Equal),
+    (0x1E, Key::A),
+    (0x1F, Key::O),
+    (0x20, Key::E),
+    (0x21, Key::U),
+    (0x22, Key::I),
+    (0x23, Key::D),
+    (0x24, Key::H),
+    (0x25, Key::T),
+    (0x26, Key::N),
+    (0x27, Key::S),
+    (0x28, Key::Dash),
+    (0x2C, Key::SemiColon),
+    (0x2D, Key::Q),
+    (0x2E, Key::J),
+    (0x2F, Key::K),
+    (0x30, Key::X),
+    (0x31, Key::B),
+    (0x32, Key::M),
+    (0x33, Key::W),
+    (0x34, Key::V),
+    (0x35, Key::Z),
+];
+
+impl ScanCode {
+    pub const fn new(code: u16) -> Self {
+        ScanCode(code)
+    }
+
+    pub fn code(&self) -> u16 {
+        self.0
+    }
+
+    pub fn is_extended(&self) -> bool {
+        self.0 & 0xFF00 == 0xE000
+    }
+}
+
+impl KeyboardLayout {
+    pub fn new<S: Into<String>>(name: S, table: Vec<(ScanCode, Key)>) -> Self {
+        KeyboardLayout {
+            name: name.into(),
+            table,
+        }
+    }
+
+    pub fn qwerty() -> Self {
+        KeyboardLayout::new(
+            "QWERTY",
+            QWERTY
+                .iter()
+                .map(|(code, key)| (ScanCode(*code), *key))
+                .collect(),
+        )
+    }
+
+    pub fn azerty() -> Self {
+        KeyboardLayout::qwerty().with_overrides("AZERTY", &AZERTY)
+    }
+
+    pub fn dvorak() -> Self {
+        KeyboardLayout::qwerty().with_overrides("Dvorak", &DVORAK)
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn key(&self, scancode: ScanCode) -> Option<Key> {
+        self.table
+            .iter()
+            .find(|(code, _)| *code == scancode)
+            .map(|(_, key)| *key)
+    }
+
+    pub fn scancode(&self, key: Key) -> Option<ScanCode> {
+        self.table
+            .iter()
+            .find(|(_, k)| *k == key)
+            .map(|(code, _)| *code)
+    }
+
+    // Returns the key at the same physical position on another layout
+    pub fn translate(&self, key: Key, to: &KeyboardLayout) -> Option<Key> {
+        to.key(self.scancode(key)?)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (ScanCode, Key)> + '_ {
+        self.table.iter().copied()
+    }
+
+    pub fn set(&mut self, scancode: ScanCode, key: Key) {
+        self.table
+            .retain(|(code, k)| *code != scancode && *k != key);
+        self.table.push((scancode, key));
+    }
+
+    fn with_overrides(mut self, name: &str, overrides: &[(u16, Key)]) -> Self {
+        self.name = name.to_owned();
+
+        // Keys pushed off their position without a new home are dropped
+        for (code, key) in overrides {
+            self.set(ScanCode(*code), *key);
+        }
+
+        self
+    }
+}
+
+impl Default for KeyboardLayout {
+    fn default() -> Self {
+        KeyboardLayout::qwerty()
+    }
+}
+
+impl FromStr for ScanCode {
+    type Err = ParseIntError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let digits = s
+            .strip_prefix("0x")
+            .or_else(|| s.strip_prefix("0X"))
+            .unwrap_or(s);
+        u16::from_str_radix(digits, 16).map(ScanCode)
+    }
+}
+
+impl Display for ScanCode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.is_extended() {
+            write!(f, "0x{:04X}", self.0)
+        } else {
+            write!(f, "0x{:02X}", self.0)
+        }
+    }
+}
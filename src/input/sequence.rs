@@ -0,0 +1,281 @@
+use crate::{Event, Key, MouseButton};
+use std::{
+    collections::VecDeque,
+    time::{Duration, Instant},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SequenceInput {
+    Key(Key),
+    Button(MouseButton),
+}
+
+// Inputs that must all be down at once, at least one of them newly pressed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SequenceStep {
+    inputs: Vec<SequenceInput>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Sequence {
+    steps: Vec<SequenceStep>,
+    step_window: Duration,
+    priority: i32,
+}
+
+pub struct SequenceMatcher<A> {
+    sequences: Vec<(Sequence, A)>,
+    simultaneous_window: Duration,
+    buffer_duration: Duration,
+    held: Vec<SequenceInput>,
+    history: VecDeque<PressGroup>,
+}
+
+struct PressGroup {
+    time: Instant,
+    pressed: Vec<SequenceInput>,
+    held: Vec<SequenceInput>,
+}
+
+impl SequenceStep {
+    pub fn new(inputs: Vec<SequenceInput>) -> Self {
+        assert!(!inputs.is_empty());
+        SequenceStep { inputs }
+    }
+
+    pub fn inputs(&self) -> &[SequenceInput] {
+        &self.inputs
+    }
+
+    fn matches(&self, group: &PressGroup) -> bool {
+        self.inputs
+            .iter()
+            .all(|input| group.pressed.contains(input) || group.held.contains(input))
+            && self
+                .inputs
+                .iter()
+                .any(|input| group.pressed.contains(input))
+    }
+}
+
+impl Sequence {
+    pub fn new(steps: Vec<SequenceStep>, step_window: Duration) -> Self {
+        assert!(!steps.is_empty());
+        Sequence {
+            steps,
+            step_window,
+            priority: 0,
+        }
+    }
+
+    pub fn with_priority(mut self, priority: i32) -> Self {
+        self.priority = priority;
+        self
+    }
+
+    pub fn steps(&self) -> &[SequenceStep] {
+        &self.steps
+    }
+
+    pub fn step_window(&self) -> Duration {
+        self.step_window
+    }
+
+    pub fn priority(&self) -> i32 {
+        self.priority
+    }
+
+    fn matches(&self, history: &VecDeque<PressGroup>) -> bool {
+        self.matches_prefix(self.steps.len(), history)
+    }
+
+    // Whether the history ends with the first few steps, so more presses could complete it
+    fn is_in_progress(&self, history: &VecDeque<PressGroup>) -> bool {
+        (1..self.steps.len()).any(|count| self.matches_prefix(count, history))
+    }
+
+    // Whether the last `count` groups of the history match the first `count` steps
+    fn matches_prefix(&self, count: usize, history: &VecDeque<PressGroup>) -> bool {
+        if history.len() < count {
+            return false;
+        }
+
+        let groups = history.range(history.len() - count..);
+        let mut previous: Option<Instant> = None;
+        for (step, group) in self.steps[..count].iter().zip(groups) {
+            if !step.matches(group) {
+                return false;
+            }
+
+            if let Some(previous) = previous {
+                if group.time.saturating_duration_since(previous) > self.step_window {
+                    return false;
+                }
+            }
+            previous = Some(group.time);
+        }
+
+        true
+    }
+
+    fn rank(&self) -> (i32, usize) {
+        (self.priority, self.steps.len())
+    }
+}
+
+impl<A> SequenceMatcher<A> {
+    pub fn new(simultaneous_window: Duration, buffer_duration: Duration) -> Self {
+        SequenceMatcher {
+            sequences: Vec::new(),
+            simultaneous_window,
+            buffer_duration,
+            held: Vec::new(),
+            history: VecDeque::new(),
+        }
+    }
+
+    pub fn register(&mut self, sequence: Sequence, action: A) {
+        self.sequences.push((sequence, action));
+    }
+
+    pub fn simultaneous_window(&self) -> Duration {
+        self.simultaneous_window
+    }
+
+    pub fn set_simultaneous_window(&mut self, window: Duration) {
+        self.simultaneous_window = window;
+    }
+
+    pub fn buffer_duration(&self) -> Duration {
+        self.buffer_duration
+    }
+
+    pub fn set_buffer_duration(&mut self, duration: Duration) {
+        self.buffer_duration = duration;
+    }
+
+    pub fn press(&mut self, input: SequenceInput, time: Instant) -> Option<&A> {
+        if self.held.contains(&input) {
+            return None;
+        }
+
+        while let Some(group) = self.history.front() {
+            if time.saturating_duration_since(group.time) <= self.buffer_duration {
+                break;
+            }
+            self.history.pop_front();
+        }
+
+        match self.history.back_mut() {
+            Some(group)
+                if time.saturating_duration_since(group.time) <= self.simultaneous_window =>
+            {
+                group.pressed.push(input)
+            }
+            _ => self.history.push_back(PressGroup {
+                time,
+                pressed: vec![input],
+                held: self.held.clone(),
+            }),
+        }
+        self.held.push(input);
+
+        // Higher priority wins, then the longer sequence
+        let index = self
+            .sequences
+            .iter()
+            .enumerate()
+            .filter(|(_, (sequence, _))| sequence.matches(&self.history))
+            .max_by_key(|(_, (sequence, _))| sequence.rank())
+            .map(|(index, _)| index)?;
+
+        // The history is kept while a higher ranked sequence is partway through, so [A] firing
+        // doesn't stop [A, A, A] from completing later
+        let rank = self.sequences[index].0.rank();
+        if !self
+            .sequences
+            .iter()
+            .any(|(sequence, _)| sequence.rank() > rank && sequence.is_in_progress(&self.history))
+        {
+            self.history.clear();
+        }
+        Some(&self.sequences[index].1)
+    }
+
+    pub fn release(&mut self, input: SequenceInput) {
+        self.held.retain(|held| *held != input);
+    }
+
+    pub fn key_down(&mut self, key: Key, time: Instant) -> Option<&A> {
+        self.press(SequenceInput::Key(key), time)
+    }
+
+    pub fn key_up(&mut self, key: Key) {
+        self.release(SequenceInput::Key(key))
+    }
+
+    pub fn mouse_down(&mut self, button: MouseButton, time: Instant) -> Option<&A> {
+        self.press(SequenceInput::Button(button), time)
+    }
+
+    pub fn mouse_up(&mut self, button: MouseButton) {
+        self.release(SequenceInput::Button(button))
+    }
+
+    pub fn handle_event(&mut self, event: &Event, time: Instant) -> Option<&A> {
+        match *event {
+            Event::KeyDown(key) => self.key_down(key, time),
+            Event::MouseDown(button) => self.mouse_down(button, time),
+            Event::KeyUp(key) => {
+                self.key_up(key);
+                None
+            }
+            Event::MouseUp(button) => {
+                self.mouse_up(button);
+                None
+            }
+            _ => None,
+        }
+    }
+
+    pub fn reset(&mut self) {
+        self.held.clear();
+        self.history.clear();
+    }
+}
+
+impl<A> Default for SequenceMatcher<A> {
+    fn default() -> Self {
+        SequenceMatcher::new(Duration::from_millis(50), Duration::from_millis(1000))
+    }
+}
+
+impl From<Key> for SequenceInput {
+    fn from(key: Key) -> Self {
+        SequenceInput::Key(key)
+    }
+}
+
+impl From<MouseButton> for SequenceInput {
+    fn from(button: MouseButton) -> Self {
+        SequenceInput::Button(button)
+    }
+}
+
+impl From<SequenceInput> for SequenceStep {
+    fn from(input: SequenceInput) -> Self {
+        SequenceStep::new(vec![input])
+    }
+}
+
+impl From<Key> for SequenceStep {
+    fn from(key: Key) -> Self {
+        SequenceInput::Key(key).into()
+    }
+}
+
+impl From<MouseButton> for SequenceStep {
+    fn from(button: MouseButton) -> Self {
+        SequenceInput::Button(button).into()
+    }
+}
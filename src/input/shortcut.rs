@@ -0,0 +1,455 @@
+use crate::{Key, KeyNames};
+use std::{
+    collections::VecDeque,
+    fmt::Display,
+    ops::{BitOr, BitOrAssign},
+    str::FromStr,
+    time::{Duration, Instant},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Modifiers(u8);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct KeyStroke {
+    modifiers: Modifiers,
+    key: Key,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Shortcut {
+    strokes: Vec<KeyStroke>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseShortcutError {
+    Empty,
+    UnknownKey(String),
+}
+
+pub struct ShortcutMatcher<A> {
+    shortcuts: Vec<(Shortcut, A)>,
+    timeout: Duration,
+    held: Vec<Key>,
+    pending: Vec<KeyStroke>,
+    // Exact match of the pending strokes held back because a longer chord starts with them
+    matched: Option<usize>,
+    // Shortcuts that fired but haven't been returned yet
+    ready: VecDeque<usize>,
+    last_stroke: Option<Instant>,
+}
+
+const MODIFIER_NAMES: [(Modifiers, &str); 4] = [
+    (Modifiers::CONTROL, "Ctrl"),
+    (Modifiers::SHIFT, "Shift"),
+    (Modifiers::ALT, "Alt"),
+    (Modifiers::WINDOWS, "Win"),
+];
+
+impl Modifiers {
+    pub const NONE: Modifiers = Modifiers(0);
+    pub const CONTROL: Modifiers = Modifiers(1 << 0);
+    pub const SHIFT: Modifiers = Modifiers(1 << 1);
+    pub const ALT: Modifiers = Modifiers(1 << 2);
+    pub const WINDOWS: Modifiers = Modifiers(1 << 3);
+
+    // Left and right variants map to the same modifier
+    pub fn from_key(key: Key) -> Option<Modifiers> {
+        match key {
+            Key::LeftControl | Key::RightControl => Some(Modifiers::CONTROL),
+            Key::LeftShift | Key::RightShift => Some(Modifiers::SHIFT),
+            Key::LeftAlt | Key::RightAlt => Some(Modifiers::ALT),
+            Key::Windows => Some(Modifiers::WINDOWS),
+            _ => None,
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Modifiers> {
+        match name.trim().to_ascii_lowercase().as_str() {
+            "ctrl" | "control" => Some(Modifiers::CONTROL),
+            "shift" => Some(Modifiers::SHIFT),
+            "alt" => Some(Modifiers::ALT),
+            "win" | "windows" | "super" | "meta" | "cmd" | "command" => Some(Modifiers::WINDOWS),
+            _ => None,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+
+    pub fn contains(&self, other: Modifiers) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    pub fn insert(&mut self, other: Modifiers) {
+        self.0 |= other.0;
+    }
+
+    pub fn remove(&mut self, other: Modifiers) {
+        self.0 &= !other.0;
+    }
+
+    // Each contained modifier on its own, in display order
+    pub fn iter(&self) -> impl Iterator<Item = Modifiers> + '_ {
+        MODIFIER_NAMES
+            .iter()
+            .map(|(modifier, _)| *modifier)
+            .filter(|modifier| self.contains(*modifier))
+    }
+}
+
+impl KeyStroke {
+    pub fn new(modifiers: Modifiers, key: Key) -> Self {
+        KeyStroke { modifiers, key }
+    }
+
+    pub fn modifiers(&self) -> Modifiers {
+        self.modifiers
+    }
+
+    pub fn key(&self) -> Key {
+        self.key
+    }
+
+    pub fn display_with<N: KeyNames + ?Sized>(&self, names: &N) -> String {
+        let mut string = String::new();
+        for modifier in self.modifiers.iter() {
+            string.push_str(&names.modifier_name(modifier));
+            string.push_str(names.separator());
+        }
+        string.push_str(&names.key_name(self.key));
+        string
+    }
+}
+
+impl Shortcut {
+    pub fn new(strokes: Vec<KeyStroke>) -> Self {
+        assert!(!strokes.is_empty());
+        Shortcut { strokes }
+    }
+
+    pub fn single(modifiers: Modifiers, key: Key) -> Self {
+        Shortcut::new(vec![KeyStroke::new(modifiers, key)])
+    }
+
+    pub fn strokes(&self) -> &[KeyStroke] {
+        &self.strokes
+    }
+
+    pub fn is_chord(&self) -> bool {
+        self.strokes.len() > 1
+    }
+
+    pub fn display_with<N: KeyNames + ?Sized>(&self, names: &N) -> String {
+        self.strokes
+            .iter()
+            .map(|stroke| stroke.display_with(names))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+impl<A> ShortcutMatcher<A> {
+    pub const DEFAULT_TIMEOUT: Duration = Duration::from_millis(1000);
+
+    pub fn new(timeout: Duration) -> Self {
+        ShortcutMatcher {
+            shortcuts: Vec::new(),
+            timeout,
+            held: Vec::new(),
+            pending: Vec::new(),
+            matched: None,
+            ready: VecDeque::new(),
+            last_stroke: None,
+        }
+    }
+
+    pub fn register(&mut self, shortcut: Shortcut, action: A) {
+        self.shortcuts.push((shortcut, action));
+    }
+
+    pub fn unregister(&mut self, shortcut: &Shortcut) -> Option<A> {
+        let index = self.shortcuts.iter().position(|(s, _)| s == shortcut)?;
+        self.clear_pending();
+        self.ready.clear();
+        Some(self.shortcuts.remove(index).1)
+    }
+
+    pub fn timeout(&self) -> Duration {
+        self.timeout
+    }
+
+    pub fn set_timeout(&mut self, timeout: Duration) {
+        self.timeout = timeout;
+    }
+
+    pub fn modifiers(&self) -> Modifiers {
+        let mut modifiers = Modifiers::NONE;
+        for key in &self.held {
+            modifiers |= Modifiers::from_key(*key).unwrap();
+        }
+        modifiers
+    }
+
+    // True while the strokes so far are the start of a registered chord
+    pub fn is_pending(&self) -> bool {
+        !self.pending.is_empty()
+    }
+
+    // A shortcut that is also the start of a longer chord only fires once the next stroke
+    // breaks the chord or the timeout passes, so call update every frame. Breaking a held back
+    // shortcut can complete a second one, it is returned by the next key_down or update.
+    pub fn key_down(&mut self, key: Key, time: Instant) -> Option<&A> {
+        if Modifiers::from_key(key).is_some() {
+            if !self.held.contains(&key) {
+                self.held.push(key);
+            }
+            return None;
+        }
+
+        self.expire(time);
+        self.last_stroke = Some(time);
+
+        self.stroke(KeyStroke::new(self.modifiers(), key));
+        self.next_ready()
+    }
+
+    pub fn key_up(&mut self, key: Key) {
+        self.held.retain(|held| *held != key);
+    }
+
+    // Fires a held back shortcut once the chord times out
+    pub fn update(&mut self, time: Instant) -> Option<&A> {
+        self.expire(time);
+        self.next_ready()
+    }
+
+    pub fn reset(&mut self) {
+        self.held.clear();
+        self.clear_pending();
+        self.ready.clear();
+        self.last_stroke = None;
+    }
+
+    fn stroke(&mut self, stroke: KeyStroke) {
+        self.pending.push(stroke);
+        if self.is_prefix() {
+            self.matched = self.find_pending();
+            return;
+        }
+
+        if let Some(index) = self.find_pending() {
+            self.ready.push_back(index);
+            self.clear_pending();
+            return;
+        }
+
+        // A stroke that breaks a chord may still start a new one
+        let restart = self.pending.len() > 1;
+        if let Some(index) = self.matched {
+            self.ready.push_back(index);
+        }
+        self.clear_pending();
+        if restart {
+            self.stroke(stroke);
+        }
+    }
+
+    fn expire(&mut self, time: Instant) {
+        if let Some(last_stroke) = self.last_stroke {
+            if time.saturating_duration_since(last_stroke) > self.timeout {
+                if let Some(index) = self.matched {
+                    self.ready.push_back(index);
+                }
+                self.clear_pending();
+            }
+        }
+    }
+
+    fn next_ready(&mut self) -> Option<&A> {
+        let index = self.ready.pop_front()?;
+        Some(&self.shortcuts[index].1)
+    }
+
+    fn clear_pending(&mut self) {
+        self.pending.clear();
+        self.matched = None;
+    }
+
+    fn find_pending(&self) -> Option<usize> {
+        self.shortcuts
+            .iter()
+            .position(|(shortcut, _)| shortcut.strokes == self.pending)
+    }
+
+    fn is_prefix(&self) -> bool {
+        self.shortcuts.iter().any(|(shortcut, _)| {
+            shortcut.strokes.len() > self.pending.len()
+                && shortcut.strokes.starts_with(&self.pending)
+        })
+    }
+}
+
+impl<A> Default for ShortcutMatcher<A> {
+    fn default() -> Self {
+        ShortcutMatcher::new(Self::DEFAULT_TIMEOUT)
+    }
+}
+
+impl BitOr for Modifiers {
+    type Output = Modifiers;
+
+    fn bitor(self, rhs: Modifiers) -> Self::Output {
+        Modifiers(self.0 | rhs.0)
+    }
+}
+
+impl BitOrAssign for Modifiers {
+    fn bitor_assign(&mut self, rhs: Modifiers) {
+        self.0 |= rhs.0;
+    }
+}
+
+impl Display for Modifiers {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut first = true;
+        for (modifier, name) in MODIFIER_NAMES {
+            if self.contains(modifier) {
+                if !first {
+                    write!(f, "+")?;
+                }
+                write!(f, "{}", name)?;
+                first = false;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for KeyStroke {
+    type Err = ParseShortcutError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        if s.is_empty() {
+            return Err(ParseShortcutError::Empty);
+        }
+
+        // Split modifiers off the front so keys containing '+' still parse
+        let mut modifiers = Modifiers::NONE;
+        let mut rest = s;
+        while let Some((head, tail)) = rest.split_once('+') {
+            match Modifiers::from_name(head) {
+                Some(modifier) if !tail.trim().is_empty() => {
+                    modifiers |= modifier;
+                    rest = tail;
+                }
+                _ => break,
+            }
+        }
+
+        let rest = rest.trim();
+        match rest.parse() {
+            Ok(key) => Ok(KeyStroke::new(modifiers, key)),
+            Err(_) => Err(ParseShortcutError::UnknownKey(rest.to_owned())),
+        }
+    }
+}
+
+impl Display for KeyStroke {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if !self.modifiers.is_empty() {
+            write!(f, "{}+", self.modifiers)?;
+        }
+        write!(f, "{}", self.key)
+    }
+}
+
+impl FromStr for Shortcut {
+    type Err = ParseShortcutError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        // Strokes are separated by whitespace, but key names may contain spaces
+        let mut strokes = Vec::new();
+        let mut current = String::new();
+        for word in s.split_whitespace() {
+            if !current.is_empty() && KeyStroke::from_str(&current).is_ok() {
+                let joined = format!("{} {}", current, word);
+                if KeyStroke::from_str(&joined).is_ok() {
+                    current = joined;
+                    continue;
+                }
+
+                strokes.push(KeyStroke::from_str(&current)?);
+                current.clear();
+            } else if !current.is_empty() {
+                current.push(' ');
+            }
+            current.push_str(word);
+        }
+
+        if current.is_empty() {
+            return Err(ParseShortcutError::Empty);
+        }
+        strokes.push(KeyStroke::from_str(&current)?);
+
+        Ok(Shortcut { strokes })
+    }
+}
+
+impl Display for Shortcut {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (i, stroke) in self.strokes.iter().enumerate() {
+            if i > 0 {
+                write!(f, " ")?;
+            }
+            write!(f, "{}", stroke)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for ParseShortcutError {}
+
+impl Display for ParseShortcutError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseShortcutError::Empty => write!(f, "Empty shortcut"),
+            ParseShortcutError::UnknownKey(key) => write!(f, "Unknown key \"{}\"", key),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn prefix_of_chord() {
+        let mut matcher = ShortcutMatcher::new(Duration::from_secs(1));
+        matcher.register("Ctrl+K".parse().unwrap(), 1);
+        matcher.register("Ctrl+K Ctrl+C".parse().unwrap(), 2);
+        matcher.register("Ctrl+S".parse().unwrap(), 3);
+
+        let time = Instant::now();
+        matcher.key_down(Key::LeftControl, time);
+
+        // The chord completes
+        assert_eq!(matcher.key_down(Key::K, time), None);
+        assert_eq!(matcher.key_down(Key::C, time), Some(&2));
+        assert!(!matcher.is_pending());
+
+        // The chord times out
+        assert_eq!(matcher.key_down(Key::K, time), None);
+        assert_eq!(matcher.update(time + Duration::from_millis(500)), None);
+        assert_eq!(matcher.update(time + Duration::from_secs(2)), Some(&1));
+
+        // Another shortcut breaks the chord
+        let time = time + Duration::from_secs(3);
+        assert_eq!(matcher.key_down(Key::K, time), None);
+        assert_eq!(matcher.key_down(Key::S, time), Some(&1));
+        assert_eq!(matcher.update(time), Some(&3));
+        assert_eq!(matcher.update(time), None);
+    }
+}
@@ -0,0 +1,542 @@
+use super::encoding::{
+    invalid_data, read_key, read_u8, read_varint, unzigzag, write_varint, zigzag,
+};
+use crate::{Input, Key, MouseButton};
+use std::{
+    collections::VecDeque,
+    io::{Read, Write},
+};
+
+// Plain state of an input for one frame, keys are stored as a bitset indexed by their value
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct InputSnapshot {
+    frame: u64,
+    keys: [u64; 4],
+    buttons: u8,
+    mouse_position: (isize, isize),
+    mouse_locked: bool,
+}
+
+pub struct InputPredictor {
+    start_frame: u64,
+    frames: VecDeque<PredictedFrame>,
+    last_confirmed: InputSnapshot,
+    max_frames_ahead: u64,
+}
+
+pub trait InputTransport {
+    fn send(&mut self, packet: &[u8]);
+    fn receive(&mut self) -> Option<Vec<u8>>;
+}
+
+// Delivers sent packets back to itself after a fixed number of frames
+#[derive(Default)]
+pub struct LoopbackTransport {
+    latency: u64,
+    frame: u64,
+    packets: VecDeque<(u64, Vec<u8>)>,
+}
+
+#[derive(Clone, Copy)]
+struct PredictedFrame {
+    snapshot: InputSnapshot,
+    confirmed: bool,
+}
+
+const FLAG_KEYS: u8 = 1 << 0;
+const FLAG_BUTTONS: u8 = 1 << 1;
+const FLAG_MOUSE_POSITION: u8 = 1 << 2;
+const FLAG_MOUSE_LOCK: u8 = 1 << 3;
+
+impl InputSnapshot {
+    pub fn new(frame: u64) -> Self {
+        InputSnapshot {
+            frame,
+            ..Default::default()
+        }
+    }
+
+    pub fn frame(&self) -> u64 {
+        self.frame
+    }
+
+    pub fn set_frame(&mut self, frame: u64) {
+        self.frame = frame;
+    }
+
+    pub fn is_key_down(&self, key: Key) -> bool {
+        let key = key as usize;
+        self.keys[key / 64] & (1 << (key % 64)) != 0
+    }
+
+    pub fn keys_down(&self) -> impl Iterator<Item = Key> + '_ {
+        Key::iter().filter(|key| self.is_key_down(*key))
+    }
+
+    pub fn is_button_down(&self, button: MouseButton) -> bool {
+        self.buttons & (1 << button as u8) != 0
+    }
+
+    pub fn mouse_position(&self) -> (isize, isize) {
+        self.mouse_position
+    }
+
+    // Compares everything except the frame number
+    pub fn same_state(&self, other: &InputSnapshot) -> bool {
+        self.keys == other.keys
+            && self.buttons == other.buttons
+            && self.mouse_position == other.mouse_position
+            && self.mouse_locked == other.mouse_locked
+    }
+
+    // Sends the changes between `previous` and this snapshot to `input`
+    pub fn apply_to<I: Input>(&self, previous: &InputSnapshot, input: &mut I) {
+        for key in Key::iter() {
+            match (previous.is_key_down(key), self.is_key_down(key)) {
+                (false, true) => input.key_down(key),
+                (true, false) => input.key_up(key),
+                _ => {}
+            }
+        }
+
+        for button in MouseButton::iter() {
+            match (previous.is_button_down(button), self.is_button_down(button)) {
+                (false, true) => input.mouse_down(button),
+                (true, false) => input.mouse_up(button),
+                _ => {}
+            }
+        }
+
+        if previous.mouse_position != self.mouse_position {
+            input.update_mouse_position(self.mouse_position);
+        }
+
+        if previous.mouse_locked != self.mouse_locked {
+            input.set_mouse_lock(self.mouse_locked);
+        }
+    }
+
+    pub fn restore<I: Input>(&self) -> I {
+        let mut input = I::new();
+        self.apply_to(&InputSnapshot::default(), &mut input);
+        input
+    }
+
+    pub fn write<W: Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        self.write_delta(&InputSnapshot::default(), writer)
+    }
+
+    pub fn read<R: Read>(reader: &mut R) -> std::io::Result<Self> {
+        InputSnapshot::default().read_delta(reader)
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.delta(&InputSnapshot::default())
+    }
+
+    pub fn from_bytes(mut bytes: &[u8]) -> std::io::Result<Self> {
+        InputSnapshot::read(&mut bytes)
+    }
+
+    pub fn delta(&self, base: &InputSnapshot) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        self.write_delta(base, &mut bytes).unwrap();
+        bytes
+    }
+
+    pub fn apply_delta(&self, mut delta: &[u8]) -> std::io::Result<InputSnapshot> {
+        self.read_delta(&mut delta)
+    }
+
+    // Only the fields that differ from `base` are written
+    pub fn write_delta<W: Write>(
+        &self,
+        base: &InputSnapshot,
+        writer: &mut W,
+    ) -> std::io::Result<()> {
+        write_varint(writer, zigzag(self.frame.wrapping_sub(base.frame) as isize))?;
+
+        let mut flags = 0;
+        if self.keys != base.keys {
+            flags |= FLAG_KEYS;
+        }
+        if self.buttons != base.buttons {
+            flags |= FLAG_BUTTONS;
+        }
+        if self.mouse_position != base.mouse_position {
+            flags |= FLAG_MOUSE_POSITION;
+        }
+        if self.mouse_locked != base.mouse_locked {
+            flags |= FLAG_MOUSE_LOCK;
+        }
+        writer.write_all(&[flags])?;
+
+        if flags & FLAG_KEYS != 0 {
+            let changed: Vec<Key> = Key::iter()
+                .filter(|key| self.is_key_down(*key) != base.is_key_down(*key))
+                .collect();
+            writer.write_all(&[changed.len() as u8])?;
+            for key in changed {
+                writer.write_all(&[key as u8])?;
+            }
+        }
+
+        if flags & FLAG_BUTTONS != 0 {
+            writer.write_all(&[self.buttons])?;
+        }
+
+        if flags & FLAG_MOUSE_POSITION != 0 {
+            // Wrapping on both sides keeps crafted deltas from overflowing
+            write_varint(
+                writer,
+                zigzag(self.mouse_position.0.wrapping_sub(base.mouse_position.0)),
+            )?;
+            write_varint(
+                writer,
+                zigzag(self.mouse_position.1.wrapping_sub(base.mouse_position.1)),
+            )?;
+        }
+
+        if flags & FLAG_MOUSE_LOCK != 0 {
+            writer.write_all(&[self.mouse_locked as u8])?;
+        }
+
+        Ok(())
+    }
+
+    pub fn read_delta<R: Read>(&self, reader: &mut R) -> std::io::Result<InputSnapshot> {
+        let mut snapshot = *self;
+        snapshot.frame = self
+            .frame
+            .wrapping_add(unzigzag(read_varint(reader)?) as u64);
+
+        let flags = read_u8(reader)?;
+        if flags & !(FLAG_KEYS | FLAG_BUTTONS | FLAG_MOUSE_POSITION | FLAG_MOUSE_LOCK) != 0 {
+            return Err(invalid_data("Invalid snapshot flags"));
+        }
+
+        if flags & FLAG_KEYS != 0 {
+            for _ in 0..read_u8(reader)? {
+                let key = read_key(reader)? as usize;
+                snapshot.keys[key / 64] ^= 1 << (key % 64);
+            }
+        }
+
+        if flags & FLAG_BUTTONS != 0 {
+            snapshot.buttons = read_u8(reader)?;
+        }
+
+        if flags & FLAG_MOUSE_POSITION != 0 {
+            snapshot.mouse_position.0 = snapshot
+                .mouse_position
+                .0
+                .wrapping_add(unzigzag(read_varint(reader)?));
+            snapshot.mouse_position.1 = snapshot
+                .mouse_position
+                .1
+                .wrapping_add(unzigzag(read_varint(reader)?));
+        }
+
+        if flags & FLAG_MOUSE_LOCK != 0 {
+            snapshot.mouse_locked = read_u8(reader)? != 0;
+        }
+
+        Ok(snapshot)
+    }
+}
+
+impl Input for InputSnapshot {
+    fn new() -> Self {
+        InputSnapshot::default()
+    }
+
+    fn key_down(&mut self, key: Key) {
+        let key = key as usize;
+        self.keys[key / 64] |= 1 << (key % 64);
+    }
+
+    fn key_up(&mut self, key: Key) {
+        let key = key as usize;
+        self.keys[key / 64] &= !(1 << (key % 64));
+    }
+
+    fn mouse_down(&mut self, key: MouseButton) {
+        self.buttons |= 1 << key as u8;
+    }
+
+    fn mouse_up(&mut self, key: MouseButton) {
+        self.buttons &= !(1 << key as u8);
+    }
+
+    fn update_mouse_position(&mut self, position: (isize, isize)) {
+        self.mouse_position = position;
+    }
+
+    fn set_mouse_lock(&mut self, state: bool) {
+        self.mouse_locked = state;
+    }
+
+    fn frame_reset(&mut self) {
+        self.frame += 1;
+    }
+
+    fn is_mouse_locked(&self) -> bool {
+        self.mouse_locked
+    }
+}
+
+impl InputPredictor {
+    pub const DEFAULT_MAX_FRAMES_AHEAD: u64 = 120;
+
+    pub fn new(start_frame: u64) -> Self {
+        InputPredictor {
+            start_frame,
+            frames: VecDeque::new(),
+            last_confirmed: InputSnapshot::new(start_frame),
+            max_frames_ahead: InputPredictor::DEFAULT_MAX_FRAMES_AHEAD,
+        }
+    }
+
+    // How far past the newest local frame a confirmed snapshot may be before it is ignored
+    pub fn set_max_frames_ahead(&mut self, max_frames_ahead: u64) {
+        self.max_frames_ahead = max_frames_ahead;
+    }
+
+    pub fn max_frames_ahead(&self) -> u64 {
+        self.max_frames_ahead
+    }
+
+    pub fn last_confirmed(&self) -> &InputSnapshot {
+        &self.last_confirmed
+    }
+
+    pub fn is_confirmed(&self, frame: u64) -> bool {
+        self.get_frame(frame)
+            .map(|frame| frame.confirmed)
+            .unwrap_or(false)
+    }
+
+    // Confirmed input for the frame, or the last confirmed input repeated
+    pub fn input(&mut self, frame: u64) -> InputSnapshot {
+        assert!(frame >= self.start_frame);
+
+        while self.start_frame + (self.frames.len() as u64) <= frame {
+            let mut snapshot = self.last_confirmed;
+            snapshot.frame = self.start_frame + self.frames.len() as u64;
+            self.frames.push_back(PredictedFrame {
+                snapshot,
+                confirmed: false,
+            });
+        }
+
+        self.get_frame(frame).unwrap().snapshot
+    }
+
+    // Returns the first frame that was mispredicted and has to be rolled back to. Snapshots
+    // from before the window or too far ahead of the local frames are ignored.
+    pub fn confirm(&mut self, snapshot: InputSnapshot) -> Option<u64> {
+        let end_frame = self.start_frame + self.frames.len() as u64;
+        if snapshot.frame < self.start_frame
+            || snapshot.frame.saturating_sub(end_frame) > self.max_frames_ahead
+        {
+            return None;
+        }
+
+        self.input(snapshot.frame);
+        let index = (snapshot.frame - self.start_frame) as usize;
+        let mispredicted = !self.frames[index].snapshot.same_state(&snapshot);
+        self.frames[index] = PredictedFrame {
+            snapshot,
+            confirmed: true,
+        };
+
+        if snapshot.frame >= self.last_confirmed.frame {
+            self.last_confirmed = snapshot;
+        }
+
+        // Later predictions were based on stale input
+        for frame in self.frames.iter_mut().skip(index + 1) {
+            if frame.confirmed {
+                break;
+            }
+            let frame_number = frame.snapshot.frame;
+            frame.snapshot = snapshot;
+            frame.snapshot.frame = frame_number;
+        }
+
+        mispredicted.then_some(snapshot.frame)
+    }
+
+    // Forgets frames before `frame`, they can no longer be rolled back to
+    pub fn discard_before(&mut self, frame: u64) {
+        while self.start_frame < frame {
+            if self.frames.pop_front().is_none() {
+                self.start_frame = frame;
+                break;
+            }
+            self.start_frame += 1;
+        }
+    }
+
+    fn get_frame(&self, frame: u64) -> Option<&PredictedFrame> {
+        frame
+            .checked_sub(self.start_frame)
+            .and_then(|index| self.frames.get(index as usize))
+    }
+}
+
+impl LoopbackTransport {
+    pub fn new(latency: u64) -> Self {
+        LoopbackTransport {
+            latency,
+            frame: 0,
+            packets: VecDeque::new(),
+        }
+    }
+
+    pub fn latency(&self) -> u64 {
+        self.latency
+    }
+
+    pub fn advance_frame(&mut self) {
+        self.frame += 1;
+    }
+}
+
+impl InputTransport for LoopbackTransport {
+    fn send(&mut self, packet: &[u8]) {
+        self.packets
+            .push_back((self.frame + self.latency, packet.to_vec()));
+    }
+
+    fn receive(&mut self) -> Option<Vec<u8>> {
+        match self.packets.front() {
+            Some((arrival, _)) if *arrival <= self.frame => {
+                self.packets.pop_front().map(|(_, packet)| packet)
+            }
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn malformed_deltas() {
+        // Mouse position delta of isize::MAX applied on top of a positive position
+        let mut base = InputSnapshot::new(0);
+        base.update_mouse_position((1, 1));
+        let mut packet = vec![0, FLAG_MOUSE_POSITION];
+        write_varint(&mut packet, zigzag(isize::MAX)).unwrap();
+        write_varint(&mut packet, zigzag(isize::MIN)).unwrap();
+        let snapshot = base.apply_delta(&packet).unwrap();
+        assert_eq!(
+            snapshot.mouse_position(),
+            (isize::MIN, isize::MIN.wrapping_add(1))
+        );
+        assert!(base
+            .apply_delta(&snapshot.delta(&base))
+            .unwrap()
+            .same_state(&snapshot));
+
+        let invalid = |bytes: &[u8]| {
+            InputSnapshot::default()
+                .apply_delta(bytes)
+                .unwrap_err()
+                .kind()
+        };
+        assert_eq!(invalid(&[]), std::io::ErrorKind::UnexpectedEof);
+        assert_eq!(invalid(&[0, 0xF0]), std::io::ErrorKind::InvalidData);
+        assert_eq!(
+            invalid(&[0, FLAG_KEYS, 1, 0xFF]),
+            std::io::ErrorKind::InvalidData
+        );
+        assert_eq!(
+            invalid(&[0, FLAG_KEYS, 2, Key::Space as u8]),
+            std::io::ErrorKind::UnexpectedEof
+        );
+        assert_eq!(invalid(&[0xFF; 11]), std::io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn confirm_far_ahead() {
+        let mut predictor = InputPredictor::new(0);
+        predictor.input(10);
+
+        let mut snapshot = InputSnapshot::new(50_000_000);
+        snapshot.key_down(Key::Space);
+        assert_eq!(predictor.confirm(snapshot), None);
+        assert!(!predictor.is_confirmed(50_000_000));
+        assert!(predictor.frames.len() < 20);
+
+        snapshot.set_frame(11 + InputPredictor::DEFAULT_MAX_FRAMES_AHEAD);
+        assert_eq!(predictor.confirm(snapshot), Some(snapshot.frame()));
+    }
+
+    // Deterministic stand-in for a game update
+    fn step(state: i64, input: &InputSnapshot) -> i64 {
+        let speed = if input.is_key_down(Key::Space) { 10 } else { 1 };
+        state * 3 % 1_000_003 + speed + input.mouse_position().0 as i64
+    }
+
+    #[test]
+    fn rollback_over_loopback() {
+        const FRAMES: u64 = 30;
+
+        // The remote player presses space on frame 5, moves the mouse on frame 12 and releases
+        // space on frame 20
+        let remote: Vec<_> = (0..FRAMES)
+            .map(|frame| {
+                let mut snapshot = InputSnapshot::new(frame);
+                if (5..20).contains(&frame) {
+                    snapshot.key_down(Key::Space);
+                }
+                if frame >= 12 {
+                    snapshot.update_mouse_position((7, -4));
+                }
+                snapshot
+            })
+            .collect();
+
+        let mut transport = LoopbackTransport::new(3);
+        let mut predictor = InputPredictor::new(0);
+        let mut sent = InputSnapshot::new(0);
+        let mut received = InputSnapshot::new(0);
+        // Game state before each frame
+        let mut states = vec![0];
+        let mut rollbacks = Vec::new();
+
+        // Keeps running after the last input so every packet arrives
+        for frame in 0..FRAMES + transport.latency() + 1 {
+            if let Some(snapshot) = remote.get(frame as usize) {
+                transport.send(&snapshot.delta(&sent));
+                sent = *snapshot;
+            }
+
+            while let Some(packet) = transport.receive() {
+                received = received.apply_delta(&packet).unwrap();
+                if let Some(rollback) = predictor.confirm(received) {
+                    rollbacks.push(rollback);
+                    states.truncate(rollback as usize + 1);
+                }
+            }
+
+            // Resimulate rolled back frames with the corrected input, then the new frame
+            let frame = frame.min(FRAMES - 1);
+            while (states.len() as u64) <= frame + 1 {
+                let simulated = states.len() as u64 - 1;
+                let input = predictor.input(simulated).restore::<InputSnapshot>();
+                states.push(step(*states.last().unwrap(), &input));
+            }
+            transport.advance_frame();
+        }
+
+        assert_eq!(rollbacks, [5, 12, 20]);
+        assert!((0..FRAMES).all(|frame| predictor.is_confirmed(frame)));
+
+        let authoritative = remote.iter().fold(0, step);
+        assert_eq!(states[FRAMES as usize], authoritative);
+    }
+}
@@ -0,0 +1,392 @@
+use crate::{Input, Vector2};
+use std::time::{Duration, Instant};
+
+#[repr(u8)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TouchPhase {
+    Started,
+    Moved,
+    Ended,
+    Cancelled,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TouchPoint {
+    id: u64,
+    position: Vector2,
+    pressure: f32,
+    phase: TouchPhase,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Gesture {
+    Tap { position: Vector2 },
+    DoubleTap { position: Vector2 },
+    LongPress { position: Vector2 },
+    Pan { position: Vector2, delta: Vector2 },
+    // Scale and angle are relative to the previous pinch or rotate
+    Pinch { center: Vector2, scale: f32 },
+    Rotate { center: Vector2, angle: f32 },
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct GestureConfig {
+    pub tap_duration: Duration,
+    pub tap_slop: f32,
+    pub double_tap_interval: Duration,
+    pub double_tap_distance: f32,
+    pub long_press_duration: Duration,
+}
+
+pub struct GestureRecognizer {
+    config: GestureConfig,
+    touches: Vec<TrackedTouch>,
+    last_tap: Option<(Instant, Vector2)>,
+    gestures: Vec<Gesture>,
+}
+
+#[derive(Default)]
+pub struct SyntheticTouchSource {
+    events: Vec<(Duration, TouchPoint)>,
+    time: Duration,
+    next_id: u64,
+}
+
+struct TrackedTouch {
+    id: u64,
+    start_position: Vector2,
+    start_time: Instant,
+    position: Vector2,
+    moved: bool,
+    long_pressed: bool,
+}
+
+impl TouchPoint {
+    pub fn new(id: u64, position: Vector2, pressure: f32, phase: TouchPhase) -> Self {
+        TouchPoint {
+            id,
+            position,
+            pressure,
+            phase,
+        }
+    }
+
+    pub fn id(&self) -> u64 {
+        self.id
+    }
+
+    pub fn position(&self) -> Vector2 {
+        self.position
+    }
+
+    pub fn pressure(&self) -> f32 {
+        self.pressure
+    }
+
+    pub fn phase(&self) -> TouchPhase {
+        self.phase
+    }
+}
+
+impl GestureRecognizer {
+    pub fn new(config: GestureConfig) -> Self {
+        GestureRecognizer {
+            config,
+            touches: Vec::new(),
+            last_tap: None,
+            gestures: Vec::new(),
+        }
+    }
+
+    pub fn config(&self) -> &GestureConfig {
+        &self.config
+    }
+
+    pub fn config_mut(&mut self) -> &mut GestureConfig {
+        &mut self.config
+    }
+
+    pub fn touch_count(&self) -> usize {
+        self.touches.len()
+    }
+
+    pub fn touch(&mut self, touch: TouchPoint, time: Instant) {
+        self.update(time);
+
+        match touch.phase {
+            TouchPhase::Started => self.touch_started(touch, time),
+            TouchPhase::Moved => self.touch_moved(touch),
+            TouchPhase::Ended => self.touch_ended(touch, time),
+            TouchPhase::Cancelled => self.touches.retain(|tracked| tracked.id != touch.id),
+        }
+    }
+
+    // Long presses fire while the touch is held, so this should be called every frame
+    pub fn update(&mut self, time: Instant) {
+        if self.touches.len() != 1 {
+            return;
+        }
+
+        let touch = &mut self.touches[0];
+        if !touch.moved
+            && !touch.long_pressed
+            && time.saturating_duration_since(touch.start_time) >= self.config.long_press_duration
+        {
+            touch.long_pressed = true;
+            self.gestures.push(Gesture::LongPress {
+                position: touch.position,
+            });
+        }
+    }
+
+    pub fn drain(&mut self) -> std::vec::Drain<'_, Gesture> {
+        self.gestures.drain(..)
+    }
+
+    pub fn reset(&mut self) {
+        self.touches.clear();
+        self.last_tap = None;
+        self.gestures.clear();
+    }
+
+    fn touch_started(&mut self, touch: TouchPoint, time: Instant) {
+        self.touches.retain(|tracked| tracked.id != touch.id);
+        self.touches.push(TrackedTouch {
+            id: touch.id,
+            start_position: touch.position,
+            start_time: time,
+            position: touch.position,
+            moved: false,
+            long_pressed: false,
+        });
+
+        // Multi-touch never produces taps or long presses
+        if self.touches.len() > 1 {
+            for tracked in &mut self.touches {
+                tracked.moved = true;
+            }
+        }
+    }
+
+    fn touch_moved(&mut self, touch: TouchPoint) {
+        let index = match self
+            .touches
+            .iter()
+            .position(|tracked| tracked.id == touch.id)
+        {
+            Some(index) => index,
+            None => return,
+        };
+
+        if self.touches.len() == 1 {
+            let tracked = &mut self.touches[0];
+            let delta = touch.position - tracked.position;
+            tracked.position = touch.position;
+
+            if !tracked.moved
+                && (touch.position - tracked.start_position).magnitude() > self.config.tap_slop
+            {
+                tracked.moved = true;
+            }
+
+            if tracked.moved {
+                self.gestures.push(Gesture::Pan {
+                    position: touch.position,
+                    delta,
+                });
+            }
+            return;
+        }
+
+        if index > 1 {
+            self.touches[index].position = touch.position;
+            return;
+        }
+
+        let old = self.touches[1].position - self.touches[0].position;
+        self.touches[index].position = touch.position;
+        let new = self.touches[1].position - self.touches[0].position;
+        let center = (self.touches[0].position + self.touches[1].position) / 2.0;
+
+        let old_distance = old.magnitude();
+        if old_distance > 0.0 && new.magnitude() != old_distance {
+            self.gestures.push(Gesture::Pinch {
+                center,
+                scale: new.magnitude() / old_distance,
+            });
+        }
+
+        let mut angle = new.y().atan2(new.x()) - old.y().atan2(old.x());
+        if angle > std::f32::consts::PI {
+            angle -= std::f32::consts::TAU;
+        } else if angle < -std::f32::consts::PI {
+            angle += std::f32::consts::TAU;
+        }
+        if angle != 0.0 {
+            self.gestures.push(Gesture::Rotate { center, angle });
+        }
+    }
+
+    fn touch_ended(&mut self, touch: TouchPoint, time: Instant) {
+        let index = match self
+            .touches
+            .iter()
+            .position(|tracked| tracked.id == touch.id)
+        {
+            Some(index) => index,
+            None => return,
+        };
+        let tracked = self.touches.remove(index);
+
+        if tracked.moved
+            || tracked.long_pressed
+            || time.saturating_duration_since(tracked.start_time) > self.config.tap_duration
+        {
+            return;
+        }
+
+        let position = tracked.position;
+        if let Some((last_time, last_position)) = self.last_tap {
+            if time.saturating_duration_since(last_time) <= self.config.double_tap_interval
+                && (position - last_position).magnitude() <= self.config.double_tap_distance
+            {
+                self.last_tap = None;
+                self.gestures.push(Gesture::DoubleTap { position });
+                return;
+            }
+        }
+
+        self.last_tap = Some((time, position));
+        self.gestures.push(Gesture::Tap { position });
+    }
+}
+
+impl Default for GestureRecognizer {
+    fn default() -> Self {
+        GestureRecognizer::new(GestureConfig::default())
+    }
+}
+
+impl Default for GestureConfig {
+    fn default() -> Self {
+        GestureConfig {
+            tap_duration: Duration::from_millis(250),
+            tap_slop: 10.0,
+            double_tap_interval: Duration::from_millis(300),
+            double_tap_distance: 40.0,
+            long_press_duration: Duration::from_millis(500),
+        }
+    }
+}
+
+impl SyntheticTouchSource {
+    const STEP: Duration = Duration::from_millis(16);
+
+    pub fn new() -> Self {
+        SyntheticTouchSource::default()
+    }
+
+    // Events paired with their offset from the start of the source
+    pub fn events(&self) -> &[(Duration, TouchPoint)] {
+        &self.events
+    }
+
+    pub fn duration(&self) -> Duration {
+        self.time
+    }
+
+    pub fn wait(&mut self, duration: Duration) -> &mut Self {
+        self.time += duration;
+        self
+    }
+
+    pub fn push(&mut self, touch: TouchPoint) -> &mut Self {
+        self.events.push((self.time, touch));
+        self
+    }
+
+    pub fn tap(&mut self, position: Vector2) -> &mut Self {
+        self.long_press(position, Self::STEP)
+    }
+
+    pub fn long_press(&mut self, position: Vector2, duration: Duration) -> &mut Self {
+        let id = self.next_id();
+        self.push(TouchPoint::new(id, position, 1.0, TouchPhase::Started))
+            .wait(duration)
+            .push(TouchPoint::new(id, position, 1.0, TouchPhase::Ended))
+            .wait(Self::STEP)
+    }
+
+    pub fn drag(&mut self, from: Vector2, to: Vector2, steps: usize) -> &mut Self {
+        let id = self.next_id();
+        self.push(TouchPoint::new(id, from, 1.0, TouchPhase::Started));
+        for step in 1..=steps {
+            let t = step as f32 / steps as f32;
+            self.wait(Self::STEP).push(TouchPoint::new(
+                id,
+                from + (to - from) * t,
+                1.0,
+                TouchPhase::Moved,
+            ));
+        }
+        self.wait(Self::STEP)
+            .push(TouchPoint::new(id, to, 1.0, TouchPhase::Ended))
+            .wait(Self::STEP)
+    }
+
+    pub fn pinch(&mut self, center: Vector2, from: f32, to: f32, steps: usize) -> &mut Self {
+        self.two_finger(center, steps, |t| (from + (to - from) * t, 0.0))
+    }
+
+    pub fn rotate(&mut self, center: Vector2, radius: f32, angle: f32, steps: usize) -> &mut Self {
+        self.two_finger(center, steps, |t| (radius * 2.0, angle * t))
+    }
+
+    pub fn feed_recognizer(&self, recognizer: &mut GestureRecognizer, start: Instant) {
+        for (offset, touch) in &self.events {
+            recognizer.touch(*touch, start + *offset);
+        }
+        recognizer.update(start + self.time);
+    }
+
+    pub fn feed_input<I: Input>(&self, input: &mut I) {
+        for (_, touch) in &self.events {
+            input.touch(*touch);
+        }
+    }
+
+    // `f` maps progress to the distance between and angle of the two fingers
+    fn two_finger<F: Fn(f32) -> (f32, f32)>(
+        &mut self,
+        center: Vector2,
+        steps: usize,
+        f: F,
+    ) -> &mut Self {
+        let ids = [self.next_id(), self.next_id()];
+        let positions = |t: f32| {
+            let (distance, angle) = f(t);
+            let offset = Vector2::new(angle.cos(), angle.sin()) * (distance / 2.0);
+            [center - offset, center + offset]
+        };
+
+        for (id, position) in ids.iter().zip(positions(0.0)) {
+            self.push(TouchPoint::new(*id, position, 1.0, TouchPhase::Started));
+        }
+        for step in 1..=steps {
+            self.wait(Self::STEP);
+            for (id, position) in ids.iter().zip(positions(step as f32 / steps as f32)) {
+                self.push(TouchPoint::new(*id, position, 1.0, TouchPhase::Moved));
+            }
+        }
+        self.wait(Self::STEP);
+        for (id, position) in ids.iter().zip(positions(1.0)) {
+            self.push(TouchPoint::new(*id, position, 1.0, TouchPhase::Ended));
+        }
+        self.wait(Self::STEP)
+    }
+
+    fn next_id(&mut self) -> u64 {
+        self.next_id += 1;
+        self.next_id
+    }
+}
@@ -1,19 +1,51 @@
+mod app;
 mod constant_buffer;
+mod cursor;
+mod debug_sink;
+mod device;
+mod dpi;
+mod error;
+mod event;
 mod format;
+mod frame_timing;
 mod input;
 mod math;
 mod mesh;
+mod monitor;
+mod scissor;
+mod screenshot;
 mod shader;
+mod state;
 mod texture;
 mod viewport;
+mod viewport_id;
+mod viewport_router;
+mod viewport_space;
 mod window;
+mod window_descriptor;
 
+pub use app::*;
 pub use constant_buffer::*;
+pub use cursor::*;
+pub use debug_sink::*;
+pub use device::*;
+pub use dpi::*;
+pub use error::*;
+pub use event::*;
 pub use format::*;
+pub use frame_timing::*;
 pub use input::*;
 pub use math::*;
 pub use mesh::*;
+pub use monitor::*;
+pub use scissor::*;
+pub use screenshot::*;
 pub use shader::*;
+pub use state::*;
 pub use texture::*;
 pub use viewport::*;
+pub use viewport_id::*;
+pub use viewport_router::*;
+pub use viewport_space::*;
 pub use window::*;
+pub use window_descriptor::*;
@@ -39,4 +39,277 @@ pub trait Matrix:
 
     fn get(&self, col: usize, row: usize) -> f32;
     fn set(&mut self, col: usize, row: usize, value: f32);
+
+    // None if the matrix is singular
+    fn inverse(&self) -> Option<Self> {
+        let mut matrix = *self;
+        let mut inverse = Self::identity();
+
+        // Scaled partial pivoting, each row is measured against its own largest element so badly
+        // scaled but invertible matrices aren't mistaken for singular ones
+        let mut scales = [0.0; 4];
+        for (row, scale) in scales.iter_mut().enumerate() {
+            *scale = (0..4)
+                .map(|col| self.get(col, row).abs())
+                .fold(0.0, f32::max);
+        }
+
+        for col in 0..4 {
+            let pivot = (col..4)
+                .max_by(|a, b| {
+                    let a = matrix.get(col, *a).abs() / scales[*a];
+                    let b = matrix.get(col, *b).abs() / scales[*b];
+                    a.total_cmp(&b)
+                })
+                .unwrap();
+            if matrix.get(col, pivot).abs() <= scales[pivot] * f32::EPSILON {
+                return None;
+            }
+
+            if pivot != col {
+                scales.swap(col, pivot);
+                for i in 0..4 {
+                    let value = matrix.get(i, col);
+                    matrix.set(i, col, matrix.get(i, pivot));
+                    matrix.set(i, pivot, value);
+
+                    let value = inverse.get(i, col);
+                    inverse.set(i, col, inverse.get(i, pivot));
+                    inverse.set(i, pivot, value);
+                }
+            }
+
+            let scale = 1.0 / matrix.get(col, col);
+            for i in 0..4 {
+                matrix.set(i, col, matrix.get(i, col) * scale);
+                inverse.set(i, col, inverse.get(i, col) * scale);
+            }
+
+            for row in (0..4).filter(|row| *row != col) {
+                let factor = matrix.get(col, row);
+                if factor == 0.0 {
+                    continue;
+                }
+                for i in 0..4 {
+                    matrix.set(i, row, matrix.get(i, row) - factor * matrix.get(i, col));
+                    inverse.set(i, row, inverse.get(i, row) - factor * inverse.get(i, col));
+                }
+            }
+        }
+
+        let finite = (0..16).all(|i| inverse.get(i % 4, i / 4).is_finite());
+        finite.then_some(inverse)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Column major, only what inverse needs is implemented
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    struct TestMatrix([f32; 16]);
+
+    impl Matrix for TestMatrix {
+        fn zero() -> Self {
+            TestMatrix([0.0; 16])
+        }
+
+        fn identity() -> Self {
+            TestMatrix::scale(1.0, 1.0, 1.0)
+        }
+
+        fn look_at(_: Vector3, _: Vector3, _: Vector3) -> Self {
+            unimplemented!()
+        }
+
+        fn scale(x: f32, y: f32, z: f32) -> Self {
+            let mut matrix = TestMatrix::zero();
+            matrix.set(0, 0, x);
+            matrix.set(1, 1, y);
+            matrix.set(2, 2, z);
+            matrix.set(3, 3, 1.0);
+            matrix
+        }
+
+        fn translation(x: f32, y: f32, z: f32) -> Self {
+            let mut matrix = TestMatrix::identity();
+            matrix.set(3, 0, x);
+            matrix.set(3, 1, y);
+            matrix.set(3, 2, z);
+            matrix
+        }
+
+        fn rotation(_: f32, _: f32, _: f32) -> Self {
+            unimplemented!()
+        }
+
+        fn rotation_x(_: f32) -> Self {
+            unimplemented!()
+        }
+
+        fn rotation_y(_: f32) -> Self {
+            unimplemented!()
+        }
+
+        fn rotation_z(_: f32) -> Self {
+            unimplemented!()
+        }
+
+        // Maps z from [near, far] to [0, 1]
+        fn orthographic(width: f32, height: f32, near: f32, far: f32) -> Self {
+            let mut matrix = TestMatrix::scale(2.0 / width, 2.0 / height, 1.0 / (far - near));
+            matrix.set(3, 2, -near / (far - near));
+            matrix
+        }
+
+        fn perspective(_: f32, _: f32, _: f32, _: f32) -> Self {
+            unimplemented!()
+        }
+
+        fn get(&self, col: usize, row: usize) -> f32 {
+            self.0[col * 4 + row]
+        }
+
+        fn set(&mut self, col: usize, row: usize, value: f32) {
+            self.0[col * 4 + row] = value;
+        }
+    }
+
+    impl Add for TestMatrix {
+        type Output = TestMatrix;
+
+        fn add(self, rhs: TestMatrix) -> TestMatrix {
+            TestMatrix(std::array::from_fn(|i| self.0[i] + rhs.0[i]))
+        }
+    }
+
+    impl AddAssign for TestMatrix {
+        fn add_assign(&mut self, rhs: TestMatrix) {
+            *self = *self + rhs;
+        }
+    }
+
+    impl Sub for TestMatrix {
+        type Output = TestMatrix;
+
+        fn sub(self, rhs: TestMatrix) -> TestMatrix {
+            TestMatrix(std::array::from_fn(|i| self.0[i] - rhs.0[i]))
+        }
+    }
+
+    impl SubAssign for TestMatrix {
+        fn sub_assign(&mut self, rhs: TestMatrix) {
+            *self = *self - rhs;
+        }
+    }
+
+    impl Mul for TestMatrix {
+        type Output = TestMatrix;
+
+        fn mul(self, rhs: TestMatrix) -> TestMatrix {
+            let mut matrix = TestMatrix::zero();
+            for col in 0..4 {
+                for row in 0..4 {
+                    let value = (0..4).map(|i| self.get(i, row) * rhs.get(col, i)).sum();
+                    matrix.set(col, row, value);
+                }
+            }
+            matrix
+        }
+    }
+
+    impl Mul<Vector4> for TestMatrix {
+        type Output = Vector4;
+
+        fn mul(self, _: Vector4) -> Vector4 {
+            unimplemented!()
+        }
+    }
+
+    impl MulAssign for TestMatrix {
+        fn mul_assign(&mut self, rhs: TestMatrix) {
+            *self = *self * rhs;
+        }
+    }
+
+    impl From<[f32; 16]> for TestMatrix {
+        fn from(values: [f32; 16]) -> Self {
+            TestMatrix(values)
+        }
+    }
+
+    impl From<TestMatrix> for [f32; 16] {
+        fn from(matrix: TestMatrix) -> Self {
+            matrix.0
+        }
+    }
+
+    impl Index<(usize, usize)> for TestMatrix {
+        type Output = f32;
+
+        fn index(&self, (col, row): (usize, usize)) -> &f32 {
+            &self.0[col * 4 + row]
+        }
+    }
+
+    impl Display for TestMatrix {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "{:?}", self.0)
+        }
+    }
+
+    // Checks `matrix * inverse` against the identity, relative to the size of each product term
+    fn assert_inverse(matrix: TestMatrix) {
+        let inverse = matrix.inverse().unwrap();
+        let product = matrix * inverse;
+        for col in 0..4 {
+            for row in 0..4 {
+                let magnitude: f32 = (0..4)
+                    .map(|i| (matrix.get(i, row) * inverse.get(col, i)).abs())
+                    .sum();
+                let expected = if col == row { 1.0 } else { 0.0 };
+                assert!(
+                    (product.get(col, row) - expected).abs() <= magnitude.max(1.0) * 1e-5,
+                    "{} * {} = {}",
+                    matrix,
+                    inverse,
+                    product
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn inverse_badly_scaled() {
+        assert_inverse(TestMatrix::scale(1e4, 1.0, 1e-4));
+        assert_inverse(TestMatrix::scale(1e-20, 1e-20, 1e-20));
+
+        let inverse = TestMatrix::scale(1e4, 1.0, 1e-4).inverse().unwrap();
+        assert_eq!(inverse.get(0, 0), 1e-4);
+        assert_eq!(inverse.get(2, 2), 1e4);
+    }
+
+    #[test]
+    fn inverse_view_projection() {
+        let projection = TestMatrix::orthographic(1920.0, 1080.0, 0.0, 1e6);
+        let view = TestMatrix::translation(-5e4, -2e3, -10.0);
+        assert_inverse(projection * view);
+    }
+
+    #[test]
+    fn inverse_singular() {
+        assert_eq!(TestMatrix::zero().inverse(), None);
+        assert_eq!(TestMatrix::scale(1.0, 0.0, 1.0).inverse(), None);
+
+        // The third row is the sum of the first two
+        let singular = TestMatrix::from([
+            1.0, 4.0, 5.0, 0.0, 2.0, 5.0, 7.0, 0.0, 3.0, 6.0, 9.0, 0.0, 0.5, 0.25, 0.75, 1.0,
+        ]);
+        assert_eq!(singular.inverse(), None);
+
+        let mut nan = TestMatrix::identity();
+        nan.set(1, 1, f32::NAN);
+        assert_eq!(nan.inverse(), None);
+    }
 }
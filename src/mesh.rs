@@ -1,36 +1,20 @@
-use crate::{Input, Window};
+use crate::{Device, Error};
 
 pub trait Mesh<V>: Sized {
-    type Window<I: Input>: Window<I>;
+    type Device: Device;
 
-    fn new<I: Input>(
-        vertices: &[V],
-        indices: &[u32],
-        window: &mut Self::Window<I>,
-    ) -> Result<Self, Box<dyn std::error::Error>>;
+    fn new(vertices: &[V], indices: &[u32], device: &Self::Device) -> Result<Self, Error>;
 
-    fn update_vertices<I: Input>(
-        &mut self,
-        vertices: &[V],
-        window: &mut Self::Window<I>,
-    ) -> Result<(), Box<dyn std::error::Error>>;
-    fn update_indices<I: Input>(
-        &mut self,
-        indices: &[u32],
-        window: &mut Self::Window<I>,
-    ) -> Result<(), Box<dyn std::error::Error>>;
+    fn update_vertices(&mut self, vertices: &[V], device: &Self::Device) -> Result<(), Error>;
+    fn update_indices(&mut self, indices: &[u32], device: &Self::Device) -> Result<(), Error>;
 
     fn render(&mut self);
 }
 
 pub trait LineMesh<V>: Sized {
-    type Window<I: Input>: Window<I>;
+    type Device: Device;
 
-    fn new<I: Input>(
-        vertices: &[V],
-        strip: bool,
-        window: &mut Self::Window<I>,
-    ) -> Result<Self, Box<dyn std::error::Error>>;
+    fn new(vertices: &[V], strip: bool, device: &Self::Device) -> Result<Self, Error>;
 
     fn render(&mut self);
 }
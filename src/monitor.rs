@@ -0,0 +1,205 @@
+use std::{fmt::Display, str::FromStr};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct VideoMode {
+    width: usize,
+    height: usize,
+    refresh_rate: u32,
+    bit_depth: u16,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Monitor {
+    name: String,
+    position: (isize, isize),
+    width: usize,
+    height: usize,
+    primary: bool,
+    video_modes: Vec<VideoMode>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum WindowMode {
+    #[default]
+    Windowed,
+    BorderlessFullscreen,
+    ExclusiveFullscreen(VideoMode),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseWindowModeError(String);
+
+impl VideoMode {
+    // Refresh rate is in millihertz
+    pub fn new(width: usize, height: usize, refresh_rate: u32, bit_depth: u16) -> Self {
+        VideoMode {
+            width,
+            height,
+            refresh_rate,
+            bit_depth,
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn refresh_rate(&self) -> u32 {
+        self.refresh_rate
+    }
+
+    pub fn refresh_rate_hz(&self) -> f32 {
+        self.refresh_rate as f32 / 1000.0
+    }
+
+    pub fn bit_depth(&self) -> u16 {
+        self.bit_depth
+    }
+}
+
+impl Monitor {
+    pub fn new<S: Into<String>>(
+        name: S,
+        position: (isize, isize),
+        width: usize,
+        height: usize,
+        primary: bool,
+        video_modes: Vec<VideoMode>,
+    ) -> Self {
+        Monitor {
+            name: name.into(),
+            position,
+            width,
+            height,
+            primary,
+            video_modes,
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    // Top left of the monitor on the virtual desktop
+    pub fn position(&self) -> (isize, isize) {
+        self.position
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn is_primary(&self) -> bool {
+        self.primary
+    }
+
+    pub fn video_modes(&self) -> &[VideoMode] {
+        &self.video_modes
+    }
+
+    // Largest resolution, then highest refresh rate and bit depth
+    pub fn best_video_mode(&self) -> Option<VideoMode> {
+        self.video_modes
+            .iter()
+            .copied()
+            .max_by_key(|mode| (mode.width * mode.height, mode.refresh_rate, mode.bit_depth))
+    }
+}
+
+impl WindowMode {
+    pub fn is_fullscreen(&self) -> bool {
+        !matches!(self, WindowMode::Windowed)
+    }
+}
+
+// Parses "WIDTHxHEIGHT" with an optional "@HZ"
+impl FromStr for VideoMode {
+    type Err = ParseWindowModeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let error = || ParseWindowModeError(s.to_owned());
+
+        let (size, refresh_rate) = match s.split_once('@') {
+            Some((size, refresh_rate)) => (
+                size,
+                refresh_rate
+                    .trim()
+                    .trim_end_matches("Hz")
+                    .trim()
+                    .parse::<f32>()
+                    .map_err(|_| error())?,
+            ),
+            None => (s, 60.0),
+        };
+
+        // Stored in millihertz, which has to be positive and fit in a u32
+        let refresh_rate = (refresh_rate * 1000.0).round();
+        if !(refresh_rate >= 1.0 && refresh_rate <= u32::MAX as f32) {
+            return Err(error());
+        }
+
+        let (width, height) = size.split_once('x').ok_or_else(error)?;
+        Ok(VideoMode::new(
+            width.trim().parse().map_err(|_| error())?,
+            height.trim().parse().map_err(|_| error())?,
+            refresh_rate as u32,
+            32,
+        ))
+    }
+}
+
+impl Display for VideoMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}x{}@{}Hz",
+            self.width,
+            self.height,
+            self.refresh_rate_hz()
+        )
+    }
+}
+
+impl FromStr for WindowMode {
+    type Err = ParseWindowModeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        match s.to_ascii_lowercase().as_str() {
+            "windowed" => Ok(WindowMode::Windowed),
+            "borderless" | "borderless fullscreen" => Ok(WindowMode::BorderlessFullscreen),
+            _ => match s.split_once(' ') {
+                Some((mode, video_mode)) if mode.eq_ignore_ascii_case("exclusive") => {
+                    Ok(WindowMode::ExclusiveFullscreen(video_mode.parse()?))
+                }
+                _ => Err(ParseWindowModeError(s.to_owned())),
+            },
+        }
+    }
+}
+
+impl Display for WindowMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            WindowMode::Windowed => write!(f, "windowed"),
+            WindowMode::BorderlessFullscreen => write!(f, "borderless"),
+            WindowMode::ExclusiveFullscreen(video_mode) => write!(f, "exclusive {}", video_mode),
+        }
+    }
+}
+
+impl std::error::Error for ParseWindowModeError {}
+
+impl Display for ParseWindowModeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Invalid window mode \"{}\"", self.0)
+    }
+}
@@ -0,0 +1,98 @@
+use crate::Vector2;
+
+// In pixels relative to the top left of the viewport
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ScissorRect {
+    top_left: Vector2,
+    size: Vector2,
+}
+
+// Nested clip regions for UI, each pushed rect is clipped to the ones below it
+#[derive(Debug, Clone, Default)]
+pub struct ScissorStack {
+    rects: Vec<ScissorRect>,
+}
+
+impl ScissorRect {
+    pub fn new(top_left: Vector2, size: Vector2) -> Self {
+        ScissorRect {
+            top_left,
+            size: Vector2::new(size.x().max(0.0), size.y().max(0.0)),
+        }
+    }
+
+    pub fn top_left(&self) -> Vector2 {
+        self.top_left
+    }
+
+    pub fn size(&self) -> Vector2 {
+        self.size
+    }
+
+    pub fn bottom_right(&self) -> Vector2 {
+        self.top_left + self.size
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.size.x() <= 0.0 || self.size.y() <= 0.0
+    }
+
+    pub fn contains(&self, position: Vector2) -> bool {
+        let bottom_right = self.bottom_right();
+        position.x() >= self.top_left.x()
+            && position.y() >= self.top_left.y()
+            && position.x() < bottom_right.x()
+            && position.y() < bottom_right.y()
+    }
+
+    // Rects that don't overlap give an empty rect
+    pub fn intersect(&self, other: &ScissorRect) -> ScissorRect {
+        let top_left = Vector2::new(
+            self.top_left.x().max(other.top_left.x()),
+            self.top_left.y().max(other.top_left.y()),
+        );
+        let bottom_right = Vector2::new(
+            self.bottom_right().x().min(other.bottom_right().x()),
+            self.bottom_right().y().min(other.bottom_right().y()),
+        );
+        ScissorRect::new(top_left, bottom_right - top_left)
+    }
+}
+
+impl ScissorStack {
+    pub fn new() -> Self {
+        ScissorStack { rects: Vec::new() }
+    }
+
+    // Returns the rect that is now in effect
+    pub fn push(&mut self, rect: ScissorRect) -> ScissorRect {
+        let rect = match self.rects.last() {
+            Some(current) => current.intersect(&rect),
+            None => rect,
+        };
+        self.rects.push(rect);
+        rect
+    }
+
+    // Returns the rect that is now in effect, None disables scissoring
+    pub fn pop(&mut self) -> Option<ScissorRect> {
+        self.rects.pop();
+        self.current()
+    }
+
+    pub fn current(&self) -> Option<ScissorRect> {
+        self.rects.last().copied()
+    }
+
+    pub fn len(&self) -> usize {
+        self.rects.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rects.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.rects.clear();
+    }
+}
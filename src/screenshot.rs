@@ -0,0 +1,449 @@
+use ginger::Pixel;
+use std::{
+    fs::File,
+    io::{BufWriter, Write},
+    path::{Path, PathBuf},
+};
+
+// Pixels are stored row by row starting at the top left
+#[derive(Clone)]
+pub struct Screenshot {
+    width: usize,
+    height: usize,
+    pixels: Vec<Pixel<u8>>,
+}
+
+pub enum CaptureTarget {
+    // Numbered PNG files in a directory
+    ImageSequence(PathBuf),
+    // Uncompressed YUV 4:4:4 video
+    Y4M(PathBuf),
+}
+
+// Writes every frame passed to it, backends call capture after presenting
+pub struct FrameCapture {
+    target: CaptureTarget,
+    frame_rate: u32,
+    frame_count: u64,
+    video: Option<VideoWriter>,
+}
+
+struct VideoWriter {
+    writer: BufWriter<File>,
+    width: usize,
+    height: usize,
+}
+
+const PNG_SIGNATURE: [u8; 8] = [0x89, b'P', b'N', b'G', b'\r', b'\n', 0x1A, b'\n'];
+const MAX_STORED_BLOCK: usize = 65535;
+
+impl Screenshot {
+    pub fn new(width: usize, height: usize, pixels: Vec<Pixel<u8>>) -> Self {
+        assert_eq!(pixels.len(), width * height);
+        Screenshot {
+            width,
+            height,
+            pixels,
+        }
+    }
+
+    // For backbuffers read bottom row first
+    pub fn from_bottom_up(width: usize, height: usize, pixels: Vec<Pixel<u8>>) -> Self {
+        let mut screenshot = Screenshot::new(width, height, pixels);
+        screenshot.flip_vertical();
+        screenshot
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn pixels(&self) -> &[Pixel<u8>] {
+        &self.pixels
+    }
+
+    pub fn into_pixels(self) -> Vec<Pixel<u8>> {
+        self.pixels
+    }
+
+    pub fn pixel(&self, x: usize, y: usize) -> Option<Pixel<u8>> {
+        if x < self.width && y < self.height {
+            Some(self.pixels[y * self.width + x])
+        } else {
+            None
+        }
+    }
+
+    pub fn rows(&self) -> std::slice::Chunks<'_, Pixel<u8>> {
+        self.pixels.chunks(self.width.max(1))
+    }
+
+    pub fn flip_vertical(&mut self) {
+        for y in 0..self.height / 2 {
+            let (top, bottom) = self.pixels.split_at_mut((self.height - y - 1) * self.width);
+            top[y * self.width..(y + 1) * self.width].swap_with_slice(&mut bottom[..self.width]);
+        }
+    }
+
+    // Clamped to the screenshot, useful for reading back a single viewport
+    pub fn crop(&self, x: usize, y: usize, width: usize, height: usize) -> Screenshot {
+        let x = x.min(self.width);
+        let y = y.min(self.height);
+        let width = width.min(self.width - x);
+        let height = height.min(self.height - y);
+
+        let mut pixels = Vec::with_capacity(width * height);
+        for row in self.rows().skip(y).take(height) {
+            pixels.extend_from_slice(&row[x..x + width]);
+        }
+        Screenshot::new(width, height, pixels)
+    }
+
+    // 8-bit RGBA, stored without compression
+    pub fn write_png<W: Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        writer.write_all(&PNG_SIGNATURE)?;
+
+        let mut header = Vec::with_capacity(13);
+        header.extend_from_slice(&(self.width as u32).to_be_bytes());
+        header.extend_from_slice(&(self.height as u32).to_be_bytes());
+        // Bit depth, RGBA, deflate, adaptive filtering, no interlace
+        header.extend_from_slice(&[8, 6, 0, 0, 0]);
+        write_chunk(writer, b"IHDR", &header)?;
+
+        let mut data = Vec::with_capacity(self.height * (self.width * 4 + 1));
+        for row in self.rows().take(self.height) {
+            data.push(0);
+            for pixel in row {
+                data.extend_from_slice(&[pixel.red(), pixel.green(), pixel.blue(), pixel.alpha()]);
+            }
+        }
+        write_chunk(writer, b"IDAT", &zlib_stored(&data))?;
+
+        write_chunk(writer, b"IEND", &[])
+    }
+
+    pub fn to_png(&self) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        self.write_png(&mut bytes).unwrap();
+        bytes
+    }
+
+    pub fn save_png<P: AsRef<Path>>(&self, path: P) -> std::io::Result<()> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        self.write_png(&mut writer)?;
+        writer.flush()
+    }
+
+    // Y, U and V planes at full resolution using BT.601 studio range, alpha is ignored
+    fn write_y4m_frame<W: Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        writer.write_all(b"FRAME\n")?;
+
+        let mut planes = [
+            Vec::with_capacity(self.pixels.len()),
+            Vec::with_capacity(self.pixels.len()),
+            Vec::with_capacity(self.pixels.len()),
+        ];
+        for pixel in &self.pixels {
+            let (r, g, b) = (
+                pixel.red() as f32,
+                pixel.green() as f32,
+                pixel.blue() as f32,
+            );
+            planes[0].push((16.0 + 0.257 * r + 0.504 * g + 0.098 * b).round() as u8);
+            planes[1].push((128.0 - 0.148 * r - 0.291 * g + 0.439 * b).round() as u8);
+            planes[2].push((128.0 + 0.439 * r - 0.368 * g - 0.071 * b).round() as u8);
+        }
+
+        for plane in &planes {
+            writer.write_all(plane)?;
+        }
+        Ok(())
+    }
+}
+
+impl FrameCapture {
+    pub fn new(target: CaptureTarget, frame_rate: u32) -> Self {
+        FrameCapture {
+            target,
+            frame_rate: frame_rate.max(1),
+            frame_count: 0,
+            video: None,
+        }
+    }
+
+    pub fn image_sequence<P: Into<PathBuf>>(directory: P) -> Self {
+        FrameCapture::new(CaptureTarget::ImageSequence(directory.into()), 60)
+    }
+
+    pub fn y4m<P: Into<PathBuf>>(path: P, frame_rate: u32) -> Self {
+        FrameCapture::new(CaptureTarget::Y4M(path.into()), frame_rate)
+    }
+
+    pub fn target(&self) -> &CaptureTarget {
+        &self.target
+    }
+
+    pub fn frame_rate(&self) -> u32 {
+        self.frame_rate
+    }
+
+    pub fn frame_count(&self) -> u64 {
+        self.frame_count
+    }
+
+    // Every frame of a video must have the size of the first one
+    pub fn capture(&mut self, frame: &Screenshot) -> std::io::Result<()> {
+        match &self.target {
+            CaptureTarget::ImageSequence(directory) => {
+                if self.frame_count == 0 {
+                    std::fs::create_dir_all(directory)?;
+                }
+                frame.save_png(directory.join(format!("frame_{:06}.png", self.frame_count)))?;
+            }
+            CaptureTarget::Y4M(path) => {
+                if self.video.is_none() {
+                    let mut writer = BufWriter::new(File::create(path)?);
+                    writeln!(
+                        writer,
+                        "YUV4MPEG2 W{} H{} F{}:1 Ip A1:1 C444",
+                        frame.width, frame.height, self.frame_rate
+                    )?;
+                    self.video = Some(VideoWriter {
+                        writer,
+                        width: frame.width,
+                        height: frame.height,
+                    });
+                }
+
+                let video = self.video.as_mut().unwrap();
+                if (video.width, video.height) != (frame.width, frame.height) {
+                    return Err(std::io::Error::new(
+                        std::io::ErrorKind::InvalidInput,
+                        format!(
+                            "Frame size {}x{} does not match the video size {}x{}",
+                            frame.width, frame.height, video.width, video.height
+                        ),
+                    ));
+                }
+                frame.write_y4m_frame(&mut video.writer)?;
+            }
+        }
+
+        self.frame_count += 1;
+        Ok(())
+    }
+
+    pub fn finish(mut self) -> std::io::Result<()> {
+        match self.video.take() {
+            Some(mut video) => video.writer.flush(),
+            None => Ok(()),
+        }
+    }
+}
+
+fn write_chunk<W: Write>(writer: &mut W, kind: &[u8; 4], data: &[u8]) -> std::io::Result<()> {
+    writer.write_all(&(data.len() as u32).to_be_bytes())?;
+    writer.write_all(kind)?;
+    writer.write_all(data)?;
+
+    let crc = crc32(crc32(0xFFFFFFFF, kind), data) ^ 0xFFFFFFFF;
+    writer.write_all(&crc.to_be_bytes())
+}
+
+fn zlib_stored(data: &[u8]) -> Vec<u8> {
+    let blocks = data.chunks(MAX_STORED_BLOCK);
+    let block_count = blocks.len().max(1);
+    let mut bytes = Vec::with_capacity(data.len() + block_count * 5 + 6);
+
+    // Deflate with a 32K window, no preset dictionary
+    bytes.extend_from_slice(&[0x78, 0x01]);
+
+    if data.is_empty() {
+        bytes.extend_from_slice(&[1, 0, 0, 0xFF, 0xFF]);
+    }
+    for (i, block) in blocks.enumerate() {
+        let length = block.len() as u16;
+        bytes.push((i + 1 == block_count) as u8);
+        bytes.extend_from_slice(&length.to_le_bytes());
+        bytes.extend_from_slice(&(!length).to_le_bytes());
+        bytes.extend_from_slice(block);
+    }
+
+    bytes.extend_from_slice(&adler32(data).to_be_bytes());
+    bytes
+}
+
+fn crc32(mut crc: u32, data: &[u8]) -> u32 {
+    for byte in data {
+        crc ^= *byte as u32;
+        for _ in 0..8 {
+            crc = if crc & 1 != 0 {
+                (crc >> 1) ^ 0xEDB88320
+            } else {
+                crc >> 1
+            };
+        }
+    }
+    crc
+}
+
+fn adler32(data: &[u8]) -> u32 {
+    let (mut a, mut b) = (1u32, 0u32);
+    // Largest chunk that cannot overflow before the modulo
+    for chunk in data.chunks(5552) {
+        for byte in chunk {
+            a += *byte as u32;
+            b += a;
+        }
+        a %= 65521;
+        b %= 65521;
+    }
+    (b << 16) | a
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Splits a stored zlib stream into its blocks, checking the header, lengths and checksum
+    fn stored_blocks(zlib: &[u8]) -> Vec<(bool, &[u8])> {
+        assert_eq!(&zlib[..2], &[0x78, 0x01]);
+
+        let mut blocks = Vec::new();
+        let mut rest = &zlib[2..];
+        loop {
+            let last = rest[0] == 1;
+            let length = u16::from_le_bytes([rest[1], rest[2]]);
+            assert_eq!(!length, u16::from_le_bytes([rest[3], rest[4]]));
+            let (block, remaining) = rest[5..].split_at(length as usize);
+            blocks.push((last, block));
+            rest = remaining;
+            if last {
+                break;
+            }
+        }
+
+        let data: Vec<u8> = blocks
+            .iter()
+            .flat_map(|(_, block)| *block)
+            .copied()
+            .collect();
+        assert_eq!(rest, adler32(&data).to_be_bytes());
+        blocks
+    }
+
+    #[test]
+    fn checksums() {
+        assert_eq!(crc32(0xFFFFFFFF, b"123456789") ^ 0xFFFFFFFF, 0xCBF43926);
+        assert_eq!(crc32(0xFFFFFFFF, b"IEND") ^ 0xFFFFFFFF, 0xAE426082);
+        assert_eq!(crc32(0xFFFFFFFF, b"") ^ 0xFFFFFFFF, 0);
+
+        assert_eq!(adler32(b"Wikipedia"), 0x11E60398);
+        assert_eq!(adler32(b""), 1);
+
+        // Long enough to need the periodic modulo
+        let data = vec![0xFF; 100_000];
+        let (mut a, mut b) = (1u64, 0u64);
+        for byte in &data {
+            a = (a + *byte as u64) % 65521;
+            b = (b + a) % 65521;
+        }
+        assert_eq!(adler32(&data), ((b << 16) | a) as u32);
+    }
+
+    #[test]
+    fn stored_block_split() {
+        let data: Vec<u8> = (0..MAX_STORED_BLOCK * 2 + 10).map(|i| i as u8).collect();
+        let zlib = zlib_stored(&data);
+        let blocks = stored_blocks(&zlib);
+        let lengths: Vec<_> = blocks
+            .iter()
+            .map(|(last, block)| (*last, block.len()))
+            .collect();
+        assert_eq!(
+            lengths,
+            [
+                (false, MAX_STORED_BLOCK),
+                (false, MAX_STORED_BLOCK),
+                (true, 10)
+            ]
+        );
+        assert_eq!(zlib.len(), 2 + data.len() + 3 * 5 + 4);
+
+        assert_eq!(stored_blocks(&zlib_stored(&[])), [(true, &[][..])]);
+    }
+
+    #[test]
+    fn png_layout() {
+        // 80100 bytes of image data, more than one stored block
+        let (width, height) = (200, 100);
+        let pixels = (0..width * height)
+            .map(|i| Pixel::new(i as u8, (i / width) as u8, 7, 255))
+            .collect();
+        let png = Screenshot::new(width, height, pixels).to_png();
+        assert_eq!(&png[..8], &PNG_SIGNATURE);
+
+        let mut chunks = Vec::new();
+        let mut rest = &png[8..];
+        while !rest.is_empty() {
+            let length = u32::from_be_bytes(rest[..4].try_into().unwrap()) as usize;
+            let (kind, data) = (&rest[4..8], &rest[8..8 + length]);
+            let crc = u32::from_be_bytes(rest[8 + length..12 + length].try_into().unwrap());
+            assert_eq!(crc, crc32(crc32(0xFFFFFFFF, kind), data) ^ 0xFFFFFFFF);
+            chunks.push((kind, data));
+            rest = &rest[12 + length..];
+        }
+
+        let kinds: Vec<_> = chunks.iter().map(|(kind, _)| *kind).collect();
+        assert_eq!(kinds, [b"IHDR", b"IDAT", b"IEND"]);
+        assert_eq!(chunks[0].1, [0, 0, 0, 200, 0, 0, 0, 100, 8, 6, 0, 0, 0]);
+
+        let blocks = stored_blocks(chunks[1].1);
+        assert_eq!(blocks.len(), 2);
+        let data: Vec<u8> = blocks
+            .iter()
+            .flat_map(|(_, block)| *block)
+            .copied()
+            .collect();
+        assert_eq!(data.len(), height * (width * 4 + 1));
+
+        // Each row starts with filter type 0 followed by RGBA
+        let row = &data[3 * (width * 4 + 1)..];
+        assert_eq!(&row[..9], &[0, 88, 3, 7, 255, 89, 3, 7, 255]);
+    }
+
+    #[test]
+    fn y4m_layout() {
+        let path = std::env::temp_dir().join(format!("alexandria_y4m_{}.y4m", std::process::id()));
+        let white = Pixel::new(255, 255, 255, 255);
+        let black = Pixel::new(0, 0, 0, 0);
+
+        let mut capture = FrameCapture::y4m(&path, 30);
+        capture
+            .capture(&Screenshot::new(2, 1, vec![white, black]))
+            .unwrap();
+        capture
+            .capture(&Screenshot::new(2, 1, vec![black, black]))
+            .unwrap();
+        assert!(capture
+            .capture(&Screenshot::new(1, 1, vec![black]))
+            .is_err());
+        assert_eq!(capture.frame_count(), 2);
+        capture.finish().unwrap();
+
+        let bytes = std::fs::read(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        let mut expected = b"YUV4MPEG2 W2 H1 F30:1 Ip A1:1 C444\n".to_vec();
+        // Y, U and V planes one after another
+        expected.extend_from_slice(b"FRAME\n");
+        expected.extend_from_slice(&[235, 16, 128, 128, 128, 128]);
+        expected.extend_from_slice(b"FRAME\n");
+        expected.extend_from_slice(&[16, 16, 128, 128, 128, 128]);
+        assert_eq!(bytes, expected);
+    }
+}
@@ -1,13 +1,13 @@
-use crate::{Format, Input, Window};
+use crate::{Device, Error, Format};
 
 pub trait Shader: Sized {
-    type Window<I: Input>: Window<I>;
+    type Device: Device;
 
-    fn new<S: AsRef<str>, I: Input>(
+    fn new<S: AsRef<str>>(
         code: S,
         vertex_layout: &[(&str, Format)],
-        window: &mut Self::Window<I>,
-    ) -> Result<Self, Box<dyn std::error::Error>>;
+        device: &Self::Device,
+    ) -> Result<Self, Error>;
 
     fn set_active(&mut self);
     fn clear_active(&mut self);
@@ -0,0 +1,623 @@
+use crate::{Input, Window};
+
+// Screens such as menus, gameplay and pause overlays. Times are in seconds.
+pub trait State<I: Input, W: Window<I>> {
+    fn enter(&mut self, _window: &mut W) {}
+    fn exit(&mut self, _window: &mut W) {}
+
+    // Another state was pushed on top of this one or the one above it was popped
+    fn pause(&mut self, _window: &mut W) {}
+    fn resume(&mut self, _window: &mut W) {}
+
+    // `has_input` is false while a state above blocks input, the window input should be ignored.
+    // Only the top state's transition is applied, states updated underneath a non-blocking
+    // overlay should return Transition::None since anything else is dropped.
+    fn update(&mut self, window: &mut W, delta: f32, has_input: bool) -> Transition<I, W>;
+    fn fixed_update(&mut self, _window: &mut W, _timestep: f32, _has_input: bool) {}
+    fn render(&mut self, window: &mut W, alpha: f32);
+
+    // An opaque full screen state blocks everything, an overlay like a pause menu only blocks
+    // input and updates so the game stays visible but frozen underneath
+    fn blocks_input(&self) -> bool {
+        true
+    }
+
+    fn blocks_update(&self) -> bool {
+        true
+    }
+
+    fn blocks_render(&self) -> bool {
+        true
+    }
+}
+
+pub enum Transition<I: Input, W: Window<I>> {
+    None,
+    Push(Box<dyn State<I, W>>),
+    Pop,
+    Replace(Box<dyn State<I, W>>),
+    // Pops every state, ending the stack
+    Quit,
+    // Plays the effect and performs the transition halfway through it
+    WithEffect(Box<Transition<I, W>>, Box<dyn TransitionEffect<I, W>>),
+}
+
+pub trait TransitionEffect<I: Input, W: Window<I>> {
+    fn duration(&self) -> f32;
+
+    // Drawn over the states, progress goes from 0.0 to 1.0 and back to 0.0 with the transition
+    // performed at the peak
+    fn render(&mut self, window: &mut W, progress: f32);
+}
+
+pub struct StateStack<I: Input, W: Window<I>> {
+    states: Vec<Box<dyn State<I, W>>>,
+    effect: Option<ActiveEffect<I, W>>,
+}
+
+struct ActiveEffect<I: Input, W: Window<I>> {
+    effect: Box<dyn TransitionEffect<I, W>>,
+    transition: Option<Transition<I, W>>,
+    elapsed: f32,
+}
+
+impl<I: Input, W: Window<I>> Transition<I, W> {
+    pub fn with_effect<E: TransitionEffect<I, W> + 'static>(self, effect: E) -> Self {
+        Transition::WithEffect(Box::new(self), Box::new(effect))
+    }
+
+    pub fn is_none(&self) -> bool {
+        matches!(self, Transition::None)
+    }
+}
+
+impl<I: Input, W: Window<I>> StateStack<I, W> {
+    pub fn new() -> Self {
+        StateStack {
+            states: Vec::new(),
+            effect: None,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.states.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.states.is_empty()
+    }
+
+    pub fn is_transitioning(&self) -> bool {
+        self.effect.is_some()
+    }
+
+    pub fn push(&mut self, window: &mut W, mut state: Box<dyn State<I, W>>) {
+        if let Some(top) = self.states.last_mut() {
+            top.pause(window);
+        }
+        state.enter(window);
+        self.states.push(state);
+    }
+
+    pub fn pop(&mut self, window: &mut W) -> Option<Box<dyn State<I, W>>> {
+        let mut state = self.states.pop()?;
+        state.exit(window);
+        if let Some(top) = self.states.last_mut() {
+            top.resume(window);
+        }
+        Some(state)
+    }
+
+    // The state below is not resumed in between
+    pub fn replace(
+        &mut self,
+        window: &mut W,
+        mut state: Box<dyn State<I, W>>,
+    ) -> Option<Box<dyn State<I, W>>> {
+        let old = self.states.pop().map(|mut old| {
+            old.exit(window);
+            old
+        });
+        state.enter(window);
+        self.states.push(state);
+        old
+    }
+
+    pub fn clear(&mut self, window: &mut W) {
+        while let Some(mut state) = self.states.pop() {
+            state.exit(window);
+        }
+        self.effect = None;
+    }
+
+    pub fn apply(&mut self, window: &mut W, transition: Transition<I, W>) {
+        match transition {
+            Transition::None => {}
+            Transition::Push(state) => self.push(window, state),
+            Transition::Pop => {
+                self.pop(window);
+            }
+            Transition::Replace(state) => {
+                self.replace(window, state);
+            }
+            Transition::Quit => self.clear(window),
+            Transition::WithEffect(transition, effect) => {
+                if self.effect.is_none() {
+                    self.effect = Some(ActiveEffect {
+                        effect,
+                        transition: Some(*transition),
+                        elapsed: 0.0,
+                    });
+                } else {
+                    // Effects don't stack, the transition happens right away instead
+                    self.apply(window, *transition);
+                }
+            }
+        }
+    }
+
+    // States are updated from the top down until one blocks updates. Only the transition
+    // returned by the top state is applied. Returns false once the stack is empty.
+    pub fn update(&mut self, window: &mut W, delta: f32) -> bool {
+        if let Some(effect) = &mut self.effect {
+            effect.elapsed += delta;
+            let duration = effect.effect.duration();
+            if effect.elapsed >= duration / 2.0 {
+                if let Some(transition) = effect.transition.take() {
+                    self.apply(window, transition);
+                }
+            }
+
+            if self
+                .effect
+                .as_ref()
+                .is_some_and(|effect| effect.elapsed >= duration)
+            {
+                self.effect = None;
+            }
+            return !self.states.is_empty();
+        }
+
+        let top = self.states.len().saturating_sub(1);
+        let mut transition = Transition::None;
+        let mut has_input = true;
+        for (i, state) in self.states.iter_mut().enumerate().rev() {
+            let result = state.update(window, delta, has_input);
+            if i == top {
+                transition = result;
+            }
+
+            has_input &= !state.blocks_input();
+            if state.blocks_update() {
+                break;
+            }
+        }
+
+        self.apply(window, transition);
+        !self.states.is_empty()
+    }
+
+    pub fn fixed_update(&mut self, window: &mut W, timestep: f32) {
+        if self.effect.is_some() {
+            return;
+        }
+
+        let mut has_input = true;
+        for state in self.states.iter_mut().rev() {
+            state.fixed_update(window, timestep, has_input);
+
+            has_input &= !state.blocks_input();
+            if state.blocks_update() {
+                break;
+            }
+        }
+    }
+
+    // Renders from the highest state that blocks rendering upwards, then the active effect
+    pub fn render(&mut self, window: &mut W, alpha: f32) {
+        let first = self
+            .states
+            .iter()
+            .rposition(|state| state.blocks_render())
+            .unwrap_or(0);
+        for state in &mut self.states[first..] {
+            state.render(window, alpha);
+        }
+
+        if let Some(effect) = &mut self.effect {
+            let duration = effect.effect.duration().max(f32::EPSILON);
+            let progress = 1.0 - (effect.elapsed / duration * 2.0 - 1.0).abs();
+            effect.effect.render(window, progress.clamp(0.0, 1.0));
+        }
+    }
+}
+
+impl<I: Input, W: Window<I>> Default for StateStack<I, W> {
+    fn default() -> Self {
+        StateStack::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        DebugSink, Device, Error, Event, FrameCapture, InputSnapshot, Monitor, PresentMode,
+        ScaleFactor, ScissorRect, ScissorStack, Screenshot, Vector2, Viewport, ViewportError,
+        ViewportId, ViewportUpdater, WindowDescriptor, WindowMode,
+    };
+    use std::rc::Rc;
+
+    // Records state callbacks, nothing else is used by the stack
+    struct TestWindow {
+        log: Vec<String>,
+        input: InputSnapshot,
+    }
+
+    enum Never {}
+
+    impl Device for Never {
+        fn new(_: bool) -> Result<Self, Error> {
+            Err(Error::DeviceLost)
+        }
+
+        fn with_debug_sink(_: Box<dyn DebugSink>) -> Result<Self, Error> {
+            Err(Error::DeviceLost)
+        }
+
+        fn set_debug_sink(&self, _: Option<Box<dyn DebugSink>>) {
+            match *self {}
+        }
+    }
+
+    impl Viewport for Never {
+        fn set_active(&mut self) {
+            match *self {}
+        }
+
+        fn update(&mut self, _: Vector2, _: Vector2) {
+            match *self {}
+        }
+
+        fn set_depth_range(&mut self, _: f32, _: f32) {
+            match *self {}
+        }
+
+        fn depth_range(&self) -> (f32, f32) {
+            match *self {}
+        }
+
+        fn set_clear_color(&mut self, _: Option<[f32; 4]>) {
+            match *self {}
+        }
+
+        fn clear_color(&self) -> Option<[f32; 4]> {
+            match *self {}
+        }
+
+        fn scissor_stack_mut(&mut self) -> &mut ScissorStack {
+            match *self {}
+        }
+
+        fn apply_scissor(&mut self, _: Option<ScissorRect>) {
+            match *self {}
+        }
+    }
+
+    impl Window<InputSnapshot> for TestWindow {
+        type Viewport = Never;
+        type Device = Never;
+
+        fn with_device(_: &WindowDescriptor, _: Rc<Never>) -> Result<Self, Error> {
+            Ok(window())
+        }
+
+        fn device(&self) -> &Rc<Never> {
+            unimplemented!()
+        }
+
+        fn poll_events(&mut self) -> bool {
+            true
+        }
+
+        fn events(&self) -> std::slice::Iter<'_, Event> {
+            [].iter()
+        }
+
+        fn begin_render(&mut self, _: [f32; 4]) {}
+
+        fn end_render(&mut self) -> Result<(), Error> {
+            Ok(())
+        }
+
+        fn present_mode(&self) -> PresentMode {
+            PresentMode::VSync
+        }
+
+        fn set_present_mode(&mut self, _: PresentMode) -> Result<(), Error> {
+            Ok(())
+        }
+
+        fn read_backbuffer(&mut self) -> Result<Screenshot, Error> {
+            unimplemented!()
+        }
+
+        fn read_viewport(&mut self, _: ViewportId) -> Result<Screenshot, Error> {
+            unimplemented!()
+        }
+
+        fn set_frame_capture(&mut self, _: Option<FrameCapture>) -> Option<FrameCapture> {
+            None
+        }
+
+        fn input(&self) -> &InputSnapshot {
+            &self.input
+        }
+
+        fn input_mut(&mut self) -> &mut InputSnapshot {
+            &mut self.input
+        }
+
+        fn width(&self) -> usize {
+            640
+        }
+
+        fn height(&self) -> usize {
+            480
+        }
+
+        fn scale_factor(&self) -> ScaleFactor {
+            ScaleFactor::ONE
+        }
+
+        fn set_debug_sink(&mut self, _: Option<Box<dyn DebugSink>>) {}
+
+        fn size_changed(&self) -> bool {
+            false
+        }
+
+        fn monitors(&self) -> Vec<Monitor> {
+            Vec::new()
+        }
+
+        fn current_monitor(&self) -> Option<Monitor> {
+            None
+        }
+
+        fn window_mode(&self) -> WindowMode {
+            WindowMode::Windowed
+        }
+
+        fn set_window_mode(&mut self, _: WindowMode) -> Result<(), Error> {
+            Ok(())
+        }
+
+        fn create_viewport(
+            &mut self,
+            _: Vector2,
+            _: Vector2,
+            _: Option<Box<dyn ViewportUpdater>>,
+        ) -> ViewportId {
+            unimplemented!()
+        }
+
+        fn set_default_viewport(&mut self, _: ViewportId) -> Result<(), ViewportError> {
+            unimplemented!()
+        }
+
+        fn update_viewport(
+            &mut self,
+            _: ViewportId,
+            _: Vector2,
+            _: Vector2,
+        ) -> Result<(), ViewportError> {
+            unimplemented!()
+        }
+
+        fn set_active_viewport(&mut self, _: ViewportId) -> Result<(), ViewportError> {
+            unimplemented!()
+        }
+
+        fn remove_viewport(&mut self, _: ViewportId) -> Result<(), ViewportError> {
+            unimplemented!()
+        }
+
+        fn set_viewport_depth_range(
+            &mut self,
+            _: ViewportId,
+            _: f32,
+            _: f32,
+        ) -> Result<(), ViewportError> {
+            unimplemented!()
+        }
+
+        fn set_viewport_clear_color(
+            &mut self,
+            _: ViewportId,
+            _: Option<[f32; 4]>,
+        ) -> Result<(), ViewportError> {
+            unimplemented!()
+        }
+
+        fn push_scissor(&mut self, _: ScissorRect) {}
+
+        fn pop_scissor(&mut self) {}
+
+        fn viewports(&self) -> Vec<ViewportId> {
+            Vec::new()
+        }
+
+        fn viewport_rect(&self, _: ViewportId) -> Result<(Vector2, Vector2), ViewportError> {
+            unimplemented!()
+        }
+    }
+
+    struct TestState {
+        name: &'static str,
+        transition: Option<Transition<InputSnapshot, TestWindow>>,
+        blocks_update: bool,
+    }
+
+    struct TestEffect;
+
+    impl TestState {
+        fn new(name: &'static str) -> Box<Self> {
+            Box::new(TestState {
+                name,
+                transition: None,
+                blocks_update: true,
+            })
+        }
+
+        fn returning(
+            name: &'static str,
+            transition: Transition<InputSnapshot, TestWindow>,
+        ) -> Box<Self> {
+            let mut state = TestState::new(name);
+            state.transition = Some(transition);
+            state
+        }
+    }
+
+    impl State<InputSnapshot, TestWindow> for TestState {
+        fn enter(&mut self, window: &mut TestWindow) {
+            window.log.push(format!("{} enter", self.name));
+        }
+
+        fn exit(&mut self, window: &mut TestWindow) {
+            window.log.push(format!("{} exit", self.name));
+        }
+
+        fn pause(&mut self, window: &mut TestWindow) {
+            window.log.push(format!("{} pause", self.name));
+        }
+
+        fn resume(&mut self, window: &mut TestWindow) {
+            window.log.push(format!("{} resume", self.name));
+        }
+
+        fn update(
+            &mut self,
+            window: &mut TestWindow,
+            _: f32,
+            _: bool,
+        ) -> Transition<InputSnapshot, TestWindow> {
+            window.log.push(format!("{} update", self.name));
+            self.transition.take().unwrap_or(Transition::None)
+        }
+
+        fn render(&mut self, window: &mut TestWindow, _: f32) {
+            window.log.push(format!("{} render", self.name));
+        }
+
+        fn blocks_update(&self) -> bool {
+            self.blocks_update
+        }
+    }
+
+    impl TransitionEffect<InputSnapshot, TestWindow> for TestEffect {
+        fn duration(&self) -> f32 {
+            1.0
+        }
+
+        fn render(&mut self, window: &mut TestWindow, progress: f32) {
+            window.log.push(format!("effect {:.1}", progress));
+        }
+    }
+
+    fn window() -> TestWindow {
+        TestWindow {
+            log: Vec::new(),
+            input: InputSnapshot::default(),
+        }
+    }
+
+    fn take_log(window: &mut TestWindow) -> Vec<String> {
+        std::mem::take(&mut window.log)
+    }
+
+    #[test]
+    fn callback_order() {
+        let mut window = window();
+        let mut stack = StateStack::new();
+
+        stack.push(&mut window, TestState::new("a"));
+        stack.push(&mut window, TestState::new("b"));
+        assert_eq!(take_log(&mut window), ["a enter", "a pause", "b enter"]);
+
+        stack.replace(&mut window, TestState::new("c"));
+        assert_eq!(take_log(&mut window), ["b exit", "c enter"]);
+
+        stack.pop(&mut window);
+        assert_eq!(take_log(&mut window), ["c exit", "a resume"]);
+
+        stack.push(&mut window, TestState::new("d"));
+        stack.clear(&mut window);
+        assert_eq!(
+            take_log(&mut window),
+            ["a pause", "d enter", "d exit", "a exit"]
+        );
+        assert!(stack.is_empty());
+    }
+
+    #[test]
+    fn only_top_transition_applies() {
+        let mut window = window();
+        let mut stack = StateStack::new();
+
+        stack.push(&mut window, TestState::returning("base", Transition::Quit));
+        let mut overlay = TestState::returning("overlay", Transition::Pop);
+        overlay.blocks_update = false;
+        stack.push(&mut window, overlay);
+        take_log(&mut window);
+
+        assert!(stack.update(&mut window, 0.1));
+        assert_eq!(
+            take_log(&mut window),
+            [
+                "overlay update",
+                "base update",
+                "overlay exit",
+                "base resume"
+            ]
+        );
+        assert_eq!(stack.len(), 1);
+    }
+
+    #[test]
+    fn effect_halfway() {
+        let mut window = window();
+        let mut stack = StateStack::new();
+
+        stack.push(
+            &mut window,
+            TestState::returning(
+                "a",
+                Transition::Push(TestState::new("b")).with_effect(TestEffect),
+            ),
+        );
+        stack.update(&mut window, 0.0);
+        assert!(stack.is_transitioning());
+        take_log(&mut window);
+
+        // Before the peak nothing changes and states aren't updated
+        stack.update(&mut window, 0.4);
+        stack.render(&mut window, 0.0);
+        assert_eq!(take_log(&mut window), ["a render", "effect 0.8"]);
+        assert_eq!(stack.len(), 1);
+
+        // The transition happens once half the duration has passed
+        stack.update(&mut window, 0.2);
+        stack.render(&mut window, 0.0);
+        assert_eq!(
+            take_log(&mut window),
+            ["a pause", "b enter", "b render", "effect 0.8"]
+        );
+        assert_eq!(stack.len(), 2);
+
+        stack.update(&mut window, 0.4);
+        assert!(!stack.is_transitioning());
+        stack.update(&mut window, 0.1);
+        assert_eq!(take_log(&mut window), ["b update"]);
+    }
+}
@@ -1,4 +1,4 @@
-use crate::{Input, Window};
+use crate::{Device, Error};
 use ginger::Pixel;
 
 pub enum SampleType {
@@ -24,15 +24,15 @@ pub trait TextureFormat {
 }
 
 pub trait Texture1D<F: TextureFormat>: Sized {
-    type Window<I: Input>: Window<I>;
+    type Device: Device;
 
-    fn new<I: Input>(
+    fn new(
         image: &[F],
         width: usize,
         height: usize,
         slot: usize,
-        window: &mut Self::Window<I>,
-    ) -> Result<Self, Box<dyn std::error::Error>>;
+        device: &Self::Device,
+    ) -> Result<Self, Error>;
 
     fn set_slot(&mut self, new_slot: usize);
 
@@ -41,16 +41,16 @@ pub trait Texture1D<F: TextureFormat>: Sized {
 }
 
 pub trait Texture2D<F: TextureFormat>: Sized {
-    type Window<I: Input>: Window<I>;
+    type Device: Device;
 
-    fn new<I: Input>(
+    fn new(
         image: &[F],
         width: usize,
         height: usize,
         slot: usize,
         sample_type: SampleType,
-        window: &mut Self::Window<I>,
-    ) -> Result<Self, Box<dyn std::error::Error>>;
+        device: &Self::Device,
+    ) -> Result<Self, Error>;
 
     fn set_slot(&mut self, new_slot: usize);
 
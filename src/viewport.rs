@@ -1,4 +1,4 @@
-use crate::Vector2;
+use crate::{Error, ScissorRect, ScissorStack, Vector2};
 
 pub trait ViewportUpdater {
     // Returns (top_left, size)
@@ -8,6 +8,28 @@ pub trait ViewportUpdater {
 pub trait Viewport {
     fn set_active(&mut self);
     fn update(&mut self, top_left: Vector2, size: Vector2);
+
+    // Depth values written are mapped into min..max, 0.0..1.0 by default
+    fn set_depth_range(&mut self, min: f32, max: f32);
+    fn depth_range(&self) -> (f32, f32);
+
+    // None leaves the viewport with the window clear color from begin_render
+    fn set_clear_color(&mut self, clear_color: Option<[f32; 4]>);
+    fn clear_color(&self) -> Option<[f32; 4]>;
+
+    fn scissor_stack_mut(&mut self) -> &mut ScissorStack;
+    // None disables scissoring
+    fn apply_scissor(&mut self, rect: Option<ScissorRect>);
+
+    fn push_scissor(&mut self, rect: ScissorRect) {
+        let rect = self.scissor_stack_mut().push(rect);
+        self.apply_scissor(Some(rect));
+    }
+
+    fn pop_scissor(&mut self) {
+        let rect = self.scissor_stack_mut().pop();
+        self.apply_scissor(rect);
+    }
 }
 
 pub struct FitScreenUpdater;
@@ -49,3 +71,236 @@ impl ViewportUpdater for FixedAspectUpdater {
         }
     }
 }
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Anchor {
+    TopLeft,
+    Top,
+    TopRight,
+    Left,
+    Center,
+    Right,
+    BottomLeft,
+    Bottom,
+    BottomRight,
+}
+
+pub struct AnchoredUpdater {
+    anchor: Anchor,
+    size: Vector2,
+    margin: Vector2,
+}
+
+pub struct MarginUpdater {
+    top_left: Vector2,
+    bottom_right: Vector2,
+}
+
+pub struct PercentageUpdater {
+    top_left: Vector2,
+    size: Vector2,
+}
+
+pub struct PixelPerfectUpdater {
+    base_size: Vector2,
+}
+
+pub struct SplitScreenUpdater {
+    player: usize,
+    players: usize,
+}
+
+impl Anchor {
+    // Fraction of the free space placed before the viewport on each axis
+    fn alignment(&self) -> Vector2 {
+        match self {
+            Anchor::TopLeft => Vector2::new(0.0, 0.0),
+            Anchor::Top => Vector2::new(0.5, 0.0),
+            Anchor::TopRight => Vector2::new(1.0, 0.0),
+            Anchor::Left => Vector2::new(0.0, 0.5),
+            Anchor::Center => Vector2::new(0.5, 0.5),
+            Anchor::Right => Vector2::new(1.0, 0.5),
+            Anchor::BottomLeft => Vector2::new(0.0, 1.0),
+            Anchor::Bottom => Vector2::new(0.5, 1.0),
+            Anchor::BottomRight => Vector2::new(1.0, 1.0),
+        }
+    }
+}
+
+impl AnchoredUpdater {
+    // Fixed size, kept `margin` away from the anchored edges
+    pub fn new(anchor: Anchor, size: Vector2, margin: Vector2) -> Self {
+        AnchoredUpdater {
+            anchor,
+            size,
+            margin,
+        }
+    }
+}
+
+impl ViewportUpdater for AnchoredUpdater {
+    fn update_viewport(&mut self, new_window_size: Vector2) -> (Vector2, Vector2) {
+        let alignment = self.anchor.alignment();
+        let free = new_window_size - self.size - self.margin * 2.0;
+        let top_left = Vector2::new(
+            self.margin.x() + free.x() * alignment.x(),
+            self.margin.y() + free.y() * alignment.y(),
+        );
+        (top_left, self.size)
+    }
+}
+
+impl MarginUpdater {
+    pub fn new(left: f32, top: f32, right: f32, bottom: f32) -> Self {
+        MarginUpdater {
+            top_left: Vector2::new(left, top),
+            bottom_right: Vector2::new(right, bottom),
+        }
+    }
+}
+
+impl ViewportUpdater for MarginUpdater {
+    fn update_viewport(&mut self, new_window_size: Vector2) -> (Vector2, Vector2) {
+        let size = new_window_size - self.top_left - self.bottom_right;
+        (
+            self.top_left,
+            Vector2::new(size.x().max(0.0), size.y().max(0.0)),
+        )
+    }
+}
+
+impl PercentageUpdater {
+    // Both are fractions of the window size, from 0.0 to 1.0
+    pub fn new(top_left: Vector2, size: Vector2) -> Self {
+        PercentageUpdater { top_left, size }
+    }
+}
+
+impl ViewportUpdater for PercentageUpdater {
+    fn update_viewport(&mut self, new_window_size: Vector2) -> (Vector2, Vector2) {
+        (self.top_left * new_window_size, self.size * new_window_size)
+    }
+}
+
+impl PixelPerfectUpdater {
+    // Scales `base_size` by the largest whole number that fits and letterboxes the rest. Windows
+    // smaller than `base_size` get a fractional scale below 1 so the viewport still fits.
+    pub fn new(base_size: Vector2) -> Result<Self, Error> {
+        if !(base_size.x() > 0.0
+            && base_size.y() > 0.0
+            && base_size.x().is_finite()
+            && base_size.y().is_finite())
+        {
+            return Err(Error::InvalidArgument(format!(
+                "Pixel perfect base size must be positive, got {}x{}",
+                base_size.x(),
+                base_size.y()
+            )));
+        }
+        Ok(PixelPerfectUpdater { base_size })
+    }
+}
+
+impl ViewportUpdater for PixelPerfectUpdater {
+    fn update_viewport(&mut self, new_window_size: Vector2) -> (Vector2, Vector2) {
+        let fit = (new_window_size.x() / self.base_size.x())
+            .min(new_window_size.y() / self.base_size.y())
+            .max(0.0);
+        let scale = if fit >= 1.0 { fit.floor() } else { fit };
+
+        let size = self.base_size * scale;
+        let top_left = (new_window_size - size) / 2.0;
+        (
+            Vector2::new(top_left.x().floor(), top_left.y().floor()),
+            size,
+        )
+    }
+}
+
+impl SplitScreenUpdater {
+    // Two players split along the longer side of the window, three put the first player on
+    // a full-width top half and four use a grid
+    pub fn new(player: usize, players: usize) -> Result<Self, Error> {
+        if !(2..=4).contains(&players) || player >= players {
+            return Err(Error::InvalidArgument(format!(
+                "Split screen player {} of {}, expected 2 to 4 players",
+                player, players
+            )));
+        }
+        Ok(SplitScreenUpdater { player, players })
+    }
+
+    pub fn layout(players: usize) -> Result<Vec<Box<dyn ViewportUpdater>>, Error> {
+        (0..players)
+            .map(|player| {
+                SplitScreenUpdater::new(player, players)
+                    .map(|updater| Box::new(updater) as Box<dyn ViewportUpdater>)
+            })
+            .collect()
+    }
+}
+
+impl ViewportUpdater for SplitScreenUpdater {
+    // The split is rounded to a whole pixel once and every cell is derived from it, so
+    // neighbouring viewports never overlap or leave a gap
+    fn update_viewport(&mut self, new_window_size: Vector2) -> (Vector2, Vector2) {
+        let (width, height) = (new_window_size.x().round(), new_window_size.y().round());
+        let (middle_x, middle_y) = ((width / 2.0).round(), (height / 2.0).round());
+        let columns = [(0.0, middle_x), (middle_x, width)];
+        let rows = [(0.0, middle_y), (middle_y, height)];
+
+        let cell = |(left, right): (f32, f32), (top, bottom): (f32, f32)| {
+            (
+                Vector2::new(left, top),
+                Vector2::new(right - left, bottom - top),
+            )
+        };
+
+        match (self.players, self.player) {
+            (2, player) if width >= height => cell(columns[player], (0.0, height)),
+            (2, player) => cell((0.0, width), rows[player]),
+            (3, 0) => cell((0.0, width), rows[0]),
+            (3, player) => cell(columns[player - 1], rows[1]),
+            (_, player) => cell(columns[player % 2], rows[player / 2]),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn split_screen_whole_pixels() {
+        assert!(SplitScreenUpdater::new(0, 1).is_err());
+        assert!(SplitScreenUpdater::new(4, 4).is_err());
+        assert!(SplitScreenUpdater::layout(5).is_err());
+
+        let window = Vector2::new(1001.0, 601.0);
+        for players in 2..=4 {
+            let rects: Vec<_> = SplitScreenUpdater::layout(players)
+                .unwrap()
+                .iter_mut()
+                .map(|updater| updater.update_viewport(window))
+                .collect();
+
+            // Whole pixels that add up to the window exactly
+            let area: f32 = rects.iter().map(|(_, size)| size.x() * size.y()).sum();
+            assert_eq!(area, window.x() * window.y());
+            for (top_left, size) in &rects {
+                for value in [top_left.x(), top_left.y(), size.x(), size.y()] {
+                    assert_eq!(value, value.round());
+                }
+                assert!(top_left.x() + size.x() <= window.x());
+                assert!(top_left.y() + size.y() <= window.y());
+            }
+        }
+    }
+
+    #[test]
+    fn pixel_perfect_invalid() {
+        assert!(PixelPerfectUpdater::new(Vector2::new(0.0, 180.0)).is_err());
+        assert!(PixelPerfectUpdater::new(Vector2::new(320.0, f32::NAN)).is_err());
+        assert!(PixelPerfectUpdater::new(Vector2::new(320.0, 180.0)).is_ok());
+    }
+}
@@ -0,0 +1,175 @@
+use std::fmt::Display;
+
+// Handle to a viewport, becomes stale once the viewport is removed even if its slot is reused
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct ViewportId {
+    index: u32,
+    generation: u32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ViewportError {
+    // The viewport was removed
+    Stale(ViewportId),
+    // The id was never handed out by this window
+    Unknown(ViewportId),
+}
+
+// Generational storage backends can keep their viewports in
+pub struct ViewportMap<T> {
+    slots: Vec<Slot<T>>,
+    free: Vec<u32>,
+    len: usize,
+}
+
+struct Slot<T> {
+    generation: u32,
+    value: Option<T>,
+}
+
+impl ViewportId {
+    pub fn new(index: u32, generation: u32) -> Self {
+        ViewportId { index, generation }
+    }
+
+    pub fn index(&self) -> u32 {
+        self.index
+    }
+
+    pub fn generation(&self) -> u32 {
+        self.generation
+    }
+}
+
+impl Display for ViewportId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}v{}", self.index, self.generation)
+    }
+}
+
+impl ViewportError {
+    pub fn id(&self) -> ViewportId {
+        match *self {
+            ViewportError::Stale(id) | ViewportError::Unknown(id) => id,
+        }
+    }
+}
+
+impl std::error::Error for ViewportError {}
+
+impl Display for ViewportError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ViewportError::Stale(id) => write!(f, "Viewport {} has been removed", id),
+            ViewportError::Unknown(id) => write!(f, "Unknown viewport {}", id),
+        }
+    }
+}
+
+impl<T> ViewportMap<T> {
+    pub fn new() -> Self {
+        ViewportMap {
+            slots: Vec::new(),
+            free: Vec::new(),
+            len: 0,
+        }
+    }
+
+    pub fn insert(&mut self, value: T) -> ViewportId {
+        self.len += 1;
+        match self.free.pop() {
+            Some(index) => {
+                let slot = &mut self.slots[index as usize];
+                slot.value = Some(value);
+                ViewportId::new(index, slot.generation)
+            }
+            None => {
+                self.slots.push(Slot {
+                    generation: 0,
+                    value: Some(value),
+                });
+                ViewportId::new(self.slots.len() as u32 - 1, 0)
+            }
+        }
+    }
+
+    pub fn remove(&mut self, id: ViewportId) -> Result<T, ViewportError> {
+        self.check(id)?;
+        let slot = &mut self.slots[id.index as usize];
+        slot.generation = slot.generation.wrapping_add(1);
+        self.free.push(id.index);
+        self.len -= 1;
+        Ok(slot.value.take().unwrap())
+    }
+
+    pub fn get(&self, id: ViewportId) -> Result<&T, ViewportError> {
+        self.check(id)?;
+        Ok(self.slots[id.index as usize].value.as_ref().unwrap())
+    }
+
+    pub fn get_mut(&mut self, id: ViewportId) -> Result<&mut T, ViewportError> {
+        self.check(id)?;
+        Ok(self.slots[id.index as usize].value.as_mut().unwrap())
+    }
+
+    pub fn contains(&self, id: ViewportId) -> bool {
+        self.check(id).is_ok()
+    }
+
+    // In slot order, not creation order
+    pub fn iter(&self) -> impl Iterator<Item = (ViewportId, &T)> + '_ {
+        self.slots.iter().enumerate().filter_map(|(index, slot)| {
+            slot.value
+                .as_ref()
+                .map(|value| (ViewportId::new(index as u32, slot.generation), value))
+        })
+    }
+
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (ViewportId, &mut T)> + '_ {
+        self.slots
+            .iter_mut()
+            .enumerate()
+            .filter_map(|(index, slot)| {
+                let generation = slot.generation;
+                slot.value
+                    .as_mut()
+                    .map(|value| (ViewportId::new(index as u32, generation), value))
+            })
+    }
+
+    pub fn ids(&self) -> impl Iterator<Item = ViewportId> + '_ {
+        self.iter().map(|(id, _)| id)
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn clear(&mut self) {
+        for (index, slot) in self.slots.iter_mut().enumerate() {
+            if slot.value.take().is_some() {
+                slot.generation = slot.generation.wrapping_add(1);
+                self.free.push(index as u32);
+            }
+        }
+        self.len = 0;
+    }
+
+    fn check(&self, id: ViewportId) -> Result<(), ViewportError> {
+        match self.slots.get(id.index as usize) {
+            Some(slot) if slot.generation == id.generation && slot.value.is_some() => Ok(()),
+            Some(slot) if id.generation < slot.generation => Err(ViewportError::Stale(id)),
+            _ => Err(ViewportError::Unknown(id)),
+        }
+    }
+}
+
+impl<T> Default for ViewportMap<T> {
+    fn default() -> Self {
+        ViewportMap::new()
+    }
+}
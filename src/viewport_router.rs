@@ -0,0 +1,202 @@
+use crate::{Event, Input, ScaleFactor, Vector2, ViewportId, ViewportSpace, Window};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum InputDevice {
+    Keyboard,
+    Mouse,
+    Gamepad(usize),
+}
+
+// Mirrors the viewports of a window so input can be routed to the one it belongs to. Rects come
+// from the window, which runs the viewport updaters, through sync or update_viewport.
+pub struct ViewportRouter {
+    viewports: Vec<RoutedViewport>,
+    mouse_position: Vector2,
+    scale_factor: ScaleFactor,
+    focused: Option<ViewportId>,
+    devices: Vec<(InputDevice, ViewportId)>,
+}
+
+struct RoutedViewport {
+    id: ViewportId,
+    top_left: Vector2,
+    size: Vector2,
+}
+
+impl ViewportRouter {
+    pub fn new() -> Self {
+        ViewportRouter {
+            viewports: Vec::new(),
+            mouse_position: Vector2::ZERO,
+            scale_factor: ScaleFactor::ONE,
+            focused: None,
+            devices: Vec::new(),
+        }
+    }
+
+    // Replaces the viewports with the window's, later ones are considered on top. Call it again
+    // once the window has recomputed them, such as after a Resize event.
+    pub fn sync<I: Input, W: Window<I>>(&mut self, window: &W) {
+        self.viewports = window
+            .viewports()
+            .into_iter()
+            .filter_map(|id| {
+                window
+                    .viewport_rect(id)
+                    .ok()
+                    .map(|(top_left, size)| RoutedViewport { id, top_left, size })
+            })
+            .collect();
+        self.scale_factor = window.scale_factor();
+
+        let viewports = &self.viewports;
+        let known = |id: &ViewportId| viewports.iter().any(|viewport| viewport.id == *id);
+        self.devices.retain(|(_, viewport)| known(viewport));
+        self.focused = self.focused.filter(known);
+    }
+
+    // Viewports added later are considered on top of earlier ones
+    pub fn add_viewport(&mut self, id: ViewportId, top_left: Vector2, size: Vector2) {
+        self.remove_viewport(id);
+        self.viewports.push(RoutedViewport { id, top_left, size });
+    }
+
+    pub fn update_viewport(&mut self, id: ViewportId, top_left: Vector2, size: Vector2) {
+        if let Some(viewport) = self.viewports.iter_mut().find(|viewport| viewport.id == id) {
+            viewport.top_left = top_left;
+            viewport.size = size;
+        }
+    }
+
+    pub fn remove_viewport(&mut self, id: ViewportId) {
+        self.viewports.retain(|viewport| viewport.id != id);
+        self.devices.retain(|(_, viewport)| *viewport != id);
+        if self.focused == Some(id) {
+            self.focused = None;
+        }
+    }
+
+    // Returns (top_left, size)
+    pub fn viewport_rect(&self, id: ViewportId) -> Option<(Vector2, Vector2)> {
+        self.viewports
+            .iter()
+            .find(|viewport| viewport.id == id)
+            .map(|viewport| (viewport.top_left, viewport.size))
+    }
+
+    pub fn viewport_space(&self, id: ViewportId) -> Option<ViewportSpace> {
+        self.viewport_rect(id)
+            .map(|(top_left, size)| ViewportSpace::new(top_left, size))
+    }
+
+    pub fn update_mouse_position(&mut self, position: (isize, isize)) {
+        self.mouse_position = Vector2::new(position.0 as f32, position.1 as f32);
+    }
+
+    pub fn mouse_position(&self) -> Vector2 {
+        self.mouse_position
+    }
+
+    pub fn logical_mouse_position(&self) -> Vector2 {
+        self.scale_factor.to_logical(self.mouse_position)
+    }
+
+    pub fn scale_factor(&self) -> ScaleFactor {
+        self.scale_factor
+    }
+
+    pub fn set_scale_factor(&mut self, scale_factor: ScaleFactor) {
+        self.scale_factor = scale_factor;
+    }
+
+    pub fn viewport_at(&self, position: Vector2) -> Option<ViewportId> {
+        self.viewports
+            .iter()
+            .rev()
+            .find(|viewport| {
+                let local = position - viewport.top_left;
+                local.x() >= 0.0
+                    && local.y() >= 0.0
+                    && local.x() < viewport.size.x()
+                    && local.y() < viewport.size.y()
+            })
+            .map(|viewport| viewport.id)
+    }
+
+    pub fn hovered(&self) -> Option<ViewportId> {
+        self.viewport_at(self.mouse_position)
+    }
+
+    // Mouse position relative to the top left of a viewport, may lie outside of it
+    pub fn local_mouse_position(&self, id: ViewportId) -> Option<Vector2> {
+        self.viewport_rect(id)
+            .map(|(top_left, _)| self.mouse_position - top_left)
+    }
+
+    pub fn local_logical_mouse_position(&self, id: ViewportId) -> Option<Vector2> {
+        self.local_mouse_position(id)
+            .map(|position| self.scale_factor.to_logical(position))
+    }
+
+    pub fn focused(&self) -> Option<ViewportId> {
+        self.focused
+    }
+
+    pub fn set_focus(&mut self, id: Option<ViewportId>) {
+        self.focused = id.filter(|id| self.viewport_rect(*id).is_some());
+    }
+
+    pub fn assign_device(&mut self, device: InputDevice, viewport: Option<ViewportId>) {
+        self.devices.retain(|(assigned, _)| *assigned != device);
+        if let Some(viewport) = viewport.filter(|id| self.viewport_rect(*id).is_some()) {
+            self.devices.push((device, viewport));
+        }
+    }
+
+    pub fn device_viewport(&self, device: InputDevice) -> Option<ViewportId> {
+        self.devices
+            .iter()
+            .find(|(assigned, _)| *assigned == device)
+            .map(|(_, viewport)| *viewport)
+    }
+
+    pub fn devices(&self, viewport: ViewportId) -> impl Iterator<Item = InputDevice> + '_ {
+        self.devices
+            .iter()
+            .filter(move |(_, assigned)| *assigned == viewport)
+            .map(|(device, _)| *device)
+    }
+
+    // Assigned devices go to their viewport, otherwise the mouse follows the cursor and
+    // everything else goes to the focused viewport
+    pub fn route(&self, device: InputDevice) -> Option<ViewportId> {
+        if let Some(viewport) = self.device_viewport(device) {
+            return Some(viewport);
+        }
+
+        match device {
+            InputDevice::Mouse => self.hovered(),
+            _ => self.focused,
+        }
+    }
+
+    // Clicking a viewport focuses it. Resizes are picked up by calling sync afterwards.
+    pub fn handle_event(&mut self, event: &Event) {
+        match *event {
+            Event::MouseMove(x, y) => self.update_mouse_position((x, y)),
+            Event::MouseDown(_) => {
+                if let Some(hovered) = self.hovered() {
+                    self.focused = Some(hovered);
+                }
+            }
+            Event::DpiChanged(scale_factor) => self.scale_factor = scale_factor,
+            _ => {}
+        }
+    }
+}
+
+impl Default for ViewportRouter {
+    fn default() -> Self {
+        ViewportRouter::new()
+    }
+}
@@ -0,0 +1,112 @@
+use crate::{Matrix, Vector2, Vector3, Vector4};
+
+// Converts between window pixels, viewport pixels, normalized device coordinates and world
+// space. Viewport rects from updaters already exclude letterboxing, so points in the bars
+// fall outside of -1.0 to 1.0 in NDC.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ViewportSpace {
+    top_left: Vector2,
+    size: Vector2,
+}
+
+impl ViewportSpace {
+    pub fn new(top_left: Vector2, size: Vector2) -> Self {
+        ViewportSpace { top_left, size }
+    }
+
+    pub fn top_left(&self) -> Vector2 {
+        self.top_left
+    }
+
+    pub fn size(&self) -> Vector2 {
+        self.size
+    }
+
+    pub fn aspect(&self) -> f32 {
+        self.size.x() / self.size.y()
+    }
+
+    pub fn contains(&self, window_position: Vector2) -> bool {
+        let local = self.window_to_viewport(window_position);
+        local.x() >= 0.0
+            && local.y() >= 0.0
+            && local.x() < self.size.x()
+            && local.y() < self.size.y()
+    }
+
+    pub fn window_to_viewport(&self, position: Vector2) -> Vector2 {
+        position - self.top_left
+    }
+
+    pub fn viewport_to_window(&self, position: Vector2) -> Vector2 {
+        position + self.top_left
+    }
+
+    // NDC has y pointing up, pixels have y pointing down
+    pub fn viewport_to_ndc(&self, position: Vector2) -> Vector2 {
+        Vector2::new(
+            position.x() / self.size.x() * 2.0 - 1.0,
+            1.0 - position.y() / self.size.y() * 2.0,
+        )
+    }
+
+    pub fn ndc_to_viewport(&self, ndc: Vector2) -> Vector2 {
+        Vector2::new(
+            (ndc.x() + 1.0) / 2.0 * self.size.x(),
+            (1.0 - ndc.y()) / 2.0 * self.size.y(),
+        )
+    }
+
+    pub fn window_to_ndc(&self, position: Vector2) -> Vector2 {
+        self.viewport_to_ndc(self.window_to_viewport(position))
+    }
+
+    pub fn ndc_to_window(&self, ndc: Vector2) -> Vector2 {
+        self.viewport_to_window(self.ndc_to_viewport(ndc))
+    }
+
+    // Depth is in the NDC depth range of the backend, None if the matrix can't be inverted
+    pub fn window_to_world<M: Matrix>(
+        &self,
+        position: Vector2,
+        depth: f32,
+        view_projection: &M,
+    ) -> Option<Vector3> {
+        let inverse = view_projection.inverse()?;
+        Some(ViewportSpace::unproject(
+            self.window_to_ndc(position),
+            depth,
+            &inverse,
+        ))
+    }
+
+    // Returns the position in the window and the NDC depth
+    pub fn world_to_window<M: Matrix>(
+        &self,
+        position: Vector3,
+        view_projection: &M,
+    ) -> (Vector2, f32) {
+        let clip = *view_projection * Vector4::new(position.x(), position.y(), position.z(), 1.0);
+        let ndc = clip.xyz() / clip.w();
+        (self.ndc_to_window(ndc.xy()), ndc.z())
+    }
+
+    // Origin on the near plane and normalized direction towards the far plane, for picking
+    pub fn window_to_ray<M: Matrix>(
+        &self,
+        position: Vector2,
+        near_depth: f32,
+        view_projection: &M,
+    ) -> Option<(Vector3, Vector3)> {
+        let inverse = view_projection.inverse()?;
+        let ndc = self.window_to_ndc(position);
+        let near = ViewportSpace::unproject(ndc, near_depth, &inverse);
+        let far = ViewportSpace::unproject(ndc, 1.0, &inverse);
+        Some((near, (far - near).normal()))
+    }
+
+    fn unproject<M: Matrix>(ndc: Vector2, depth: f32, inverse_view_projection: &M) -> Vector3 {
+        let world = *inverse_view_projection * Vector4::new(ndc.x(), ndc.y(), depth, 1.0);
+        world.xyz() / world.w()
+    }
+}
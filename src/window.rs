@@ -1,38 +1,157 @@
-use crate::{Input, Vector2, ViewportUpdater};
+use crate::{
+    CursorIcon, CursorImage, DebugSink, Device, Error, Event, FrameCapture, Input, Monitor,
+    PresentMode, ScaleFactor, ScissorRect, Screenshot, Vector2, ViewportError, ViewportId,
+    ViewportSpace, ViewportUpdater, WindowDescriptor, WindowMode,
+};
+use std::{path::Path, rc::Rc};
 
 pub trait Window<I: Input>: Sized {
     type Viewport: crate::Viewport;
+    type Device: Device;
 
-    fn new(
-        title: &str,
-        width: usize,
-        height: usize,
-        debug_logging: bool,
-    ) -> Result<Self, Box<dyn std::error::Error>>;
+    // Resources created on the device can be used by every window sharing it
+    fn with_device(descriptor: &WindowDescriptor, device: Rc<Self::Device>) -> Result<Self, Error>;
+
+    fn with_descriptor(descriptor: &WindowDescriptor) -> Result<Self, Error> {
+        Self::with_device(
+            descriptor,
+            Rc::new(Self::Device::new(descriptor.debug_logging())?),
+        )
+    }
+
+    fn new(title: &str, width: usize, height: usize, debug_logging: bool) -> Result<Self, Error> {
+        Self::with_descriptor(
+            &WindowDescriptor::new(title)
+                .with_size(width, height)
+                .with_debug_logging(debug_logging),
+        )
+    }
+
+    fn device(&self) -> &Rc<Self::Device>;
 
     fn poll_events(&mut self) -> bool;
+    // Events received by the last call to poll_events, in the order they occurred
+    fn events(&self) -> std::slice::Iter<'_, Event>;
+
+    // Files dropped onto the window since the last call to poll_events
+    fn dropped_files(&self) -> Vec<&Path> {
+        self.events()
+            .filter_map(|event| match event {
+                Event::FileDrop(path) => Some(path.as_path()),
+                _ => None,
+            })
+            .collect()
+    }
 
     fn begin_render(&mut self, clear_color: [f32; 4]);
-    fn end_render(&mut self) -> Result<(), Box<dyn std::error::Error>>;
+    fn end_render(&mut self) -> Result<(), Error>;
+
+    fn present_mode(&self) -> PresentMode;
+    // Backends without adaptive sync should fall back to VSync
+    fn set_present_mode(&mut self, mode: PresentMode) -> Result<(), Error>;
+
+    // Contents of the backbuffer as last rendered
+    fn read_backbuffer(&mut self) -> Result<Screenshot, Error>;
+    fn read_viewport(&mut self, viewport: ViewportId) -> Result<Screenshot, Error>;
+
+    // Every frame presented by end_render is passed to the capture, returns the previous one
+    fn set_frame_capture(&mut self, capture: Option<FrameCapture>) -> Option<FrameCapture>;
 
     fn input(&self) -> &I;
     fn input_mut(&mut self) -> &mut I;
 
+    // Sizes, positions and mouse coordinates are in physical pixels unless stated otherwise
     fn width(&self) -> usize;
     fn height(&self) -> usize;
 
-    fn set_debug_logging(&mut self, enable: bool);
+    fn scale_factor(&self) -> ScaleFactor;
+
+    fn physical_size(&self) -> (usize, usize) {
+        (self.width(), self.height())
+    }
+
+    fn logical_size(&self) -> Vector2 {
+        self.scale_factor().logical_size(self.physical_size())
+    }
+
+    // Platform and windowing messages, GPU messages go to the device sink
+    fn set_debug_sink(&mut self, sink: Option<Box<dyn DebugSink>>);
 
     fn size_changed(&self) -> bool;
 
+    // Backends without clipboard access return None and ignore writes
+    fn clipboard_text(&self) -> Option<String> {
+        None
+    }
+    fn set_clipboard_text(&mut self, _text: &str) {}
+
+    fn set_cursor_icon(&mut self, _icon: CursorIcon) {}
+    fn set_cursor_image(&mut self, _image: &CursorImage) {}
+    fn set_cursor_visible(&mut self, _visible: bool) {}
+    // Keeps the cursor inside the window without locking it like set_mouse_lock
+    fn set_cursor_confined(&mut self, _confined: bool) {}
+    fn warp_cursor(&mut self, _position: (isize, isize)) {}
+
+    fn monitors(&self) -> Vec<Monitor>;
+    fn current_monitor(&self) -> Option<Monitor>;
+
+    fn window_mode(&self) -> WindowMode;
+    // Viewports with an updater are recomputed and a Resize event is queued when the size changes
+    fn set_window_mode(&mut self, mode: WindowMode) -> Result<(), Error>;
+
     fn create_viewport(
         &mut self,
         top_left: Vector2,
         size: Vector2,
         updater: Option<Box<dyn ViewportUpdater>>,
-    ) -> usize;
-    fn set_default_viewport(&mut self, viewport: usize);
-    fn update_viewport(&mut self, viewport: usize, top_left: Vector2, size: Vector2);
-    fn set_active_viewport(&mut self, viewport: usize);
-    fn remove_viewport(&mut self, viewport: usize);
+    ) -> ViewportId;
+    // Updaters still receive the physical window size
+    fn create_logical_viewport(
+        &mut self,
+        top_left: Vector2,
+        size: Vector2,
+        updater: Option<Box<dyn ViewportUpdater>>,
+    ) -> ViewportId {
+        let scale_factor = self.scale_factor();
+        self.create_viewport(
+            scale_factor.to_physical(top_left),
+            scale_factor.to_physical(size),
+            updater,
+        )
+    }
+    fn set_default_viewport(&mut self, viewport: ViewportId) -> Result<(), ViewportError>;
+    fn update_viewport(
+        &mut self,
+        viewport: ViewportId,
+        top_left: Vector2,
+        size: Vector2,
+    ) -> Result<(), ViewportError>;
+    fn set_active_viewport(&mut self, viewport: ViewportId) -> Result<(), ViewportError>;
+    fn remove_viewport(&mut self, viewport: ViewportId) -> Result<(), ViewportError>;
+
+    fn set_viewport_depth_range(
+        &mut self,
+        viewport: ViewportId,
+        min: f32,
+        max: f32,
+    ) -> Result<(), ViewportError>;
+    // Viewports with a clear color are cleared to it when made active for the first time in a frame
+    fn set_viewport_clear_color(
+        &mut self,
+        viewport: ViewportId,
+        clear_color: Option<[f32; 4]>,
+    ) -> Result<(), ViewportError>;
+
+    // Scissor rects apply to the active viewport and are relative to it
+    fn push_scissor(&mut self, rect: ScissorRect);
+    fn pop_scissor(&mut self);
+
+    fn viewports(&self) -> Vec<ViewportId>;
+    // Returns (top_left, size)
+    fn viewport_rect(&self, viewport: ViewportId) -> Result<(Vector2, Vector2), ViewportError>;
+
+    fn viewport_space(&self, viewport: ViewportId) -> Result<ViewportSpace, ViewportError> {
+        self.viewport_rect(viewport)
+            .map(|(top_left, size)| ViewportSpace::new(top_left, size))
+    }
 }
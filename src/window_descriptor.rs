@@ -0,0 +1,329 @@
+use crate::{Error, PresentMode, WindowMode};
+use ginger::Pixel;
+use std::{fmt::Display, path::Path};
+
+#[derive(Clone)]
+pub struct WindowDescriptor {
+    title: String,
+    width: usize,
+    height: usize,
+    position: Option<(isize, isize)>,
+    min_size: Option<(usize, usize)>,
+    max_size: Option<(usize, usize)>,
+    resizable: bool,
+    mode: WindowMode,
+    present_mode: PresentMode,
+    msaa_samples: u32,
+    icon: Option<WindowIcon>,
+    debug_logging: bool,
+}
+
+#[derive(Clone)]
+pub struct WindowIcon {
+    pixels: Vec<Pixel<u8>>,
+    width: usize,
+    height: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseDescriptorError {
+    line: usize,
+    message: String,
+}
+
+impl WindowDescriptor {
+    pub fn new<S: Into<String>>(title: S) -> Self {
+        WindowDescriptor {
+            title: title.into(),
+            ..Default::default()
+        }
+    }
+
+    // One "key = value" pair per line, lines starting with '#' are comments
+    pub fn from_config(config: &str) -> Result<Self, ParseDescriptorError> {
+        let mut descriptor = WindowDescriptor::default();
+        for (i, line) in config.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            descriptor
+                .apply_config(line)
+                .map_err(|message| ParseDescriptorError {
+                    line: i + 1,
+                    message,
+                })?;
+        }
+        Ok(descriptor)
+    }
+
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, Error> {
+        WindowDescriptor::from_config(&std::fs::read_to_string(path)?)
+            .map_err(|error| Error::InvalidArgument(error.to_string()))
+    }
+
+    pub fn with_title<S: Into<String>>(mut self, title: S) -> Self {
+        self.title = title.into();
+        self
+    }
+
+    pub fn with_size(mut self, width: usize, height: usize) -> Self {
+        self.width = width;
+        self.height = height;
+        self
+    }
+
+    pub fn with_position(mut self, x: isize, y: isize) -> Self {
+        self.position = Some((x, y));
+        self
+    }
+
+    pub fn with_min_size(mut self, width: usize, height: usize) -> Self {
+        self.min_size = Some((width, height));
+        self
+    }
+
+    pub fn with_max_size(mut self, width: usize, height: usize) -> Self {
+        self.max_size = Some((width, height));
+        self
+    }
+
+    pub fn with_resizable(mut self, resizable: bool) -> Self {
+        self.resizable = resizable;
+        self
+    }
+
+    pub fn with_mode(mut self, mode: WindowMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    pub fn with_present_mode(mut self, present_mode: PresentMode) -> Self {
+        self.present_mode = present_mode;
+        self
+    }
+
+    // 1 disables multisampling, other counts are rounded up to 2, 4, 8 or 16
+    pub fn with_msaa(mut self, samples: u32) -> Self {
+        self.msaa_samples = samples.clamp(1, 16).next_power_of_two();
+        self
+    }
+
+    pub fn with_icon(mut self, icon: WindowIcon) -> Self {
+        self.icon = Some(icon);
+        self
+    }
+
+    pub fn with_debug_logging(mut self, debug_logging: bool) -> Self {
+        self.debug_logging = debug_logging;
+        self
+    }
+
+    pub fn title(&self) -> &str {
+        &self.title
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    // None lets the platform choose
+    pub fn position(&self) -> Option<(isize, isize)> {
+        self.position
+    }
+
+    pub fn min_size(&self) -> Option<(usize, usize)> {
+        self.min_size
+    }
+
+    pub fn max_size(&self) -> Option<(usize, usize)> {
+        self.max_size
+    }
+
+    pub fn resizable(&self) -> bool {
+        self.resizable
+    }
+
+    pub fn mode(&self) -> WindowMode {
+        self.mode
+    }
+
+    pub fn present_mode(&self) -> PresentMode {
+        self.present_mode
+    }
+
+    pub fn msaa_samples(&self) -> u32 {
+        self.msaa_samples
+    }
+
+    pub fn icon(&self) -> Option<&WindowIcon> {
+        self.icon.as_ref()
+    }
+
+    pub fn debug_logging(&self) -> bool {
+        self.debug_logging
+    }
+
+    fn apply_config(&mut self, line: &str) -> Result<(), String> {
+        let (key, value) = line
+            .split_once('=')
+            .ok_or_else(|| "Missing '='".to_owned())?;
+        let (key, value) = (key.trim(), value.trim());
+
+        let invalid = |error: &dyn Display| format!("Invalid value for \"{}\": {}", key, error);
+        let number = |value: &str| value.trim().parse::<usize>().map_err(|e| invalid(&e));
+        let flag = |value: &str| value.parse::<bool>().map_err(|e| invalid(&e));
+        let pair = |value: &str| -> Result<(usize, usize), String> {
+            let (width, height) = value
+                .split_once(',')
+                .ok_or_else(|| invalid(&"Expected \"width, height\""))?;
+            Ok((number(width)?, number(height)?))
+        };
+
+        let size = |value: &str| -> Result<(usize, usize), String> {
+            let (width, height) = pair(value)?;
+            if width == 0 || height == 0 {
+                return Err(invalid(&"Width and height must be non-zero"));
+            }
+            Ok((width, height))
+        };
+        let check_limits =
+            |min: Option<(usize, usize)>, max: Option<(usize, usize)>| match (min, max) {
+                (Some(min), Some(max)) if min.0 > max.0 || min.1 > max.1 => {
+                    Err(invalid(&"min_size is larger than max_size"))
+                }
+                _ => Ok(()),
+            };
+
+        match key {
+            "title" => self.title = value.trim_matches('"').to_owned(),
+            "size" => (self.width, self.height) = size(value)?,
+            "position" => {
+                let (x, y) = value
+                    .split_once(',')
+                    .ok_or_else(|| invalid(&"Expected \"x, y\""))?;
+                self.position = Some((
+                    x.trim().parse().map_err(|e| invalid(&e))?,
+                    y.trim().parse().map_err(|e| invalid(&e))?,
+                ));
+            }
+            "min_size" => {
+                let min_size = Some(pair(value)?);
+                check_limits(min_size, self.max_size)?;
+                self.min_size = min_size;
+            }
+            "max_size" => {
+                let max_size = Some(pair(value)?);
+                check_limits(self.min_size, max_size)?;
+                self.max_size = max_size;
+            }
+            "resizable" => self.resizable = flag(value)?,
+            "mode" => self.mode = value.parse().map_err(|e| invalid(&e))?,
+            "present_mode" => self.present_mode = value.parse().map_err(|e| invalid(&e))?,
+            "msaa" => {
+                self.msaa_samples = match value.parse::<u32>().map_err(|e| invalid(&e))? {
+                    samples @ (1 | 2 | 4 | 8 | 16) => samples,
+                    _ => return Err(invalid(&"Expected 1, 2, 4, 8 or 16 samples")),
+                }
+            }
+            "debug_logging" => self.debug_logging = flag(value)?,
+            _ => return Err(format!("Unknown key \"{}\"", key)),
+        }
+
+        Ok(())
+    }
+}
+
+impl Default for WindowDescriptor {
+    fn default() -> Self {
+        WindowDescriptor {
+            title: String::from("Alexandria"),
+            width: 1280,
+            height: 720,
+            position: None,
+            min_size: None,
+            max_size: None,
+            resizable: true,
+            mode: WindowMode::Windowed,
+            present_mode: PresentMode::VSync,
+            msaa_samples: 1,
+            icon: None,
+            debug_logging: false,
+        }
+    }
+}
+
+impl WindowIcon {
+    pub fn new(pixels: Vec<Pixel<u8>>, width: usize, height: usize) -> Self {
+        assert_eq!(pixels.len(), width * height);
+        WindowIcon {
+            pixels,
+            width,
+            height,
+        }
+    }
+
+    pub fn pixels(&self) -> &[Pixel<u8>] {
+        &self.pixels
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+}
+
+impl ParseDescriptorError {
+    pub fn line(&self) -> usize {
+        self.line
+    }
+}
+
+impl std::error::Error for ParseDescriptorError {}
+
+impl Display for ParseDescriptorError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Line {}: {}", self.line, self.message)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn error(config: &str) -> ParseDescriptorError {
+        WindowDescriptor::from_config(config).err().unwrap()
+    }
+
+    #[test]
+    fn invalid_config() {
+        let zero_size = error("title = Test\nsize = 0, 720");
+        assert_eq!(zero_size.line(), 2);
+        assert!(zero_size.message.contains("\"size\""));
+
+        for samples in ["0", "3", "32"] {
+            let msaa = error(&format!("msaa = {}", samples));
+            assert!(msaa.message.contains("\"msaa\""));
+        }
+        assert_eq!(
+            WindowDescriptor::from_config("msaa = 8")
+                .unwrap()
+                .msaa_samples(),
+            8
+        );
+
+        let limits = error("min_size = 800, 600\nmax_size = 640, 480");
+        assert_eq!(limits.line(), 2);
+        assert!(limits.message.contains("\"max_size\""));
+        let limits = error("max_size = 640, 480\nmin_size = 640, 600");
+        assert!(limits.message.contains("\"min_size\""));
+    }
+}